@@ -0,0 +1,11 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+
+#[allow(dead_code)]
+#[path = "../../src/measurement_parser.rs"]
+mod measurement_parser;
+
+fuzz_target!(|text: &str| {
+    let _ = measurement_parser::parse(text);
+});
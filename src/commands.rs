@@ -1,3 +1,9 @@
 pub mod analyze;
+pub mod check;
 pub mod download;
+pub mod export;
+pub mod migrate;
+mod run_recorder;
+mod schema_migrator;
+pub mod search;
 pub mod transform;
@@ -1,11 +1,22 @@
-use anyhow::Error;
+use anyhow::{Context, Error};
 use std::collections;
 use std::fs;
 
+use crate::commands::run_recorder;
+use crate::commands::schema_migrator;
+use crate::identity_resolver;
 use crate::measurement_parser;
+use crate::measurement_resampler;
+use crate::measurement_validator;
 
 mod database;
 
+const GRID_START_HZ: f64 = 20.0;
+
+const GRID_END_HZ: f64 = 20_000.0;
+
+const GRID_POINTS_PER_OCTAVE: u32 = 48;
+
 #[derive(clap::Parser, Debug)]
 pub struct Command {
     /// Set the input file
@@ -15,6 +26,15 @@ pub struct Command {
     /// Set the output directory
     #[arg(default_value = "out", long, short)]
     output: String,
+
+    /// Set the TOML file with brand aliases, instead of the canonical brands of a transformed
+    /// database
+    #[arg(long)]
+    aliases: Option<String>,
+
+    /// Count each unique measurement once, even when it is re-hosted by several squigs
+    #[arg(long)]
+    unique_measurements: bool,
 }
 
 #[derive(Debug)]
@@ -29,31 +49,107 @@ impl Command {
         fs::create_dir_all(&self.output)?;
 
         let transaction = connection.transaction()?;
+        schema_migrator::ensure_latest(
+            &transaction,
+            schema_migrator::Schema::Raw,
+            "run `migrate` on it to upgrade it",
+        )?;
+        if schema_migrator::version(&transaction, schema_migrator::Schema::Transformed)?.is_some() {
+            schema_migrator::ensure_latest(
+                &transaction,
+                schema_migrator::Schema::Transformed,
+                "transform the raw database again",
+            )?;
+        }
+        let run_id = run_recorder::start(&transaction, "analyze")?;
+        let mut error_count = 0;
+        let has_points = database::tables::exists(&transaction, "points")?;
+        let has_resampled_points = database::tables::exists(&transaction, "resampled_points")?;
+        let grid = measurement_resampler::grid(GRID_START_HZ, GRID_END_HZ, GRID_POINTS_PER_OCTAVE);
+        let canonical_brand_names = self.canonical_brand_names(&transaction)?;
+        let measurement_group_ids = if self.unique_measurements {
+            Some(measurement_group_ids(&transaction)?)
+        } else {
+            None
+        };
+        let mut measurement_group_pairs = collections::HashSet::new();
         let mut brand_differences = collections::HashMap::new();
-        for output in database::channel_imbalance::select(&transaction)? {
+        let outputs = if database::tables::exists(&transaction, "channel_pairs")? {
+            database::channel_imbalance::select(&transaction)?
+        } else {
+            database::channel_imbalance::select_from_channels(&transaction)?
+        };
+        for output in outputs {
+            if let Some(measurement_group_ids) = &measurement_group_ids {
+                let measurement_group_pair = (
+                    measurement_group_ids
+                        .get(&output.left_channel_id)
+                        .copied()
+                        .unwrap_or(output.left_channel_id),
+                    measurement_group_ids
+                        .get(&output.right_channel_id)
+                        .copied()
+                        .unwrap_or(output.right_channel_id),
+                );
+                if !measurement_group_pairs.insert(measurement_group_pair) {
+                    continue;
+                }
+            }
             let values = brand_differences
-                .entry(output.brand_name)
+                .entry(
+                    canonical_brand_names
+                        .get(&output.brand_id)
+                        .context("found a brand without a canonical brand")?
+                        .clone(),
+                )
                 .or_insert(Vec::<f64>::new());
-            let left_channel_points = measurement_parser::parse(&output.left_channel_text)?;
-            let right_channel_points = measurement_parser::parse(&output.right_channel_text)?;
-            if left_channel_points
-                .iter()
-                .zip(&right_channel_points)
-                .any(|(l, r)| l.frequency_hz != r.frequency_hz)
+            let left_channel_points =
+                select_points(&transaction, has_points, output.left_channel_id)?;
+            let right_channel_points =
+                select_points(&transaction, has_points, output.right_channel_id)?;
+            if left_channel_points.len() == right_channel_points.len()
+                && left_channel_points
+                    .iter()
+                    .zip(&right_channel_points)
+                    .all(|(l, r)| l.frequency_hz == r.frequency_hz && l.spl_db == r.spl_db)
             {
                 continue;
             }
-            let left_channel_average = left_channel_points
+            if measurement_validator::quality(&left_channel_points)
+                == measurement_validator::Quality::Error
+                || measurement_validator::quality(&right_channel_points)
+                    == measurement_validator::Quality::Error
+            {
+                error_count += 1;
+                continue;
+            }
+            let (left_resampled_points, right_resampled_points) = if has_resampled_points {
+                (
+                    database::resampled_points::select(&transaction, output.left_channel_id)?,
+                    database::resampled_points::select(&transaction, output.right_channel_id)?,
+                )
+            } else {
+                (
+                    measurement_resampler::resample(&left_channel_points, &grid),
+                    measurement_resampler::resample(&right_channel_points, &grid),
+                )
+            };
+            let right_channel_spl_db: collections::HashMap<usize, f64> = right_resampled_points
                 .iter()
-                .map(|point| point.spl_db)
-                .sum::<f64>()
-                / left_channel_points.len() as f64;
-            let right_channel_average = right_channel_points
+                .map(|point| (point.idx, point.spl_db))
+                .collect();
+            let differences: Vec<f64> = left_resampled_points
                 .iter()
-                .map(|point| point.spl_db)
-                .sum::<f64>()
-                / right_channel_points.len() as f64;
-            values.push((left_channel_average - right_channel_average).abs());
+                .filter_map(|point| {
+                    right_channel_spl_db
+                        .get(&point.idx)
+                        .map(|spl_db| point.spl_db - spl_db)
+                })
+                .collect();
+            if differences.is_empty() {
+                continue;
+            }
+            values.push((differences.iter().sum::<f64>() / differences.len() as f64).abs());
         }
         let mut brand_differences: Vec<Bar> = brand_differences
             .iter()
@@ -113,8 +209,62 @@ impl Command {
             keys.len() * 20,
             1.0,
         );
+        run_recorder::finish(&transaction, run_id, error_count)?;
         transaction.commit()?;
 
         Ok(())
     }
+
+    fn canonical_brand_names(
+        &self,
+        transaction: &rusqlite::Transaction,
+    ) -> Result<collections::HashMap<i64, String>, Error> {
+        if self.aliases.is_none()
+            && database::tables::exists(transaction, "brand_canonical_brands")?
+        {
+            return Ok(database::canonical_brands::select(transaction)?
+                .into_iter()
+                .map(|brand| (brand.brand_id, brand.name))
+                .collect());
+        }
+        let aliases = match &self.aliases {
+            Some(path) => identity_resolver::Aliases::load(path)?,
+            None => identity_resolver::Aliases::default(),
+        };
+        let brand_resolution =
+            identity_resolver::resolve_brands(&database::brands::select(transaction)?, &aliases);
+        Ok(brand_resolution
+            .brand_canonical_brands
+            .into_iter()
+            .map(|(brand_id, canonical_brand_idx)| {
+                (
+                    brand_id,
+                    brand_resolution.canonical_brands[canonical_brand_idx].clone(),
+                )
+            })
+            .collect())
+    }
+}
+
+fn measurement_group_ids(
+    transaction: &rusqlite::Transaction,
+) -> Result<collections::HashMap<i64, i64>, Error> {
+    let measurement_groups = if database::tables::exists(transaction, "measurement_groups")? {
+        database::measurement_groups::select(transaction)?
+    } else {
+        database::channels::select_text_groups(transaction)?
+    };
+    Ok(measurement_groups.into_iter().collect())
+}
+
+fn select_points(
+    transaction: &rusqlite::Transaction,
+    has_points: bool,
+    channel_id: i64,
+) -> Result<Vec<measurement_parser::Point>, Error> {
+    if has_points {
+        database::points::select(transaction, channel_id)
+    } else {
+        measurement_parser::parse(&database::channels::select_text(transaction, channel_id)?)
+    }
 }
@@ -1 +1,8 @@
+pub mod brands;
+pub mod canonical_brands;
 pub mod channel_imbalance;
+pub mod channels;
+pub mod measurement_groups;
+pub mod points;
+pub mod resampled_points;
+pub mod tables;
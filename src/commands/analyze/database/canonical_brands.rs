@@ -0,0 +1,29 @@
+use anyhow::Error;
+use indoc::indoc;
+
+#[derive(Debug)]
+pub struct BrandIdAndName {
+    pub brand_id: i64,
+    pub name: String,
+}
+
+pub fn select(transaction: &rusqlite::Transaction) -> Result<Vec<BrandIdAndName>, Error> {
+    let query = indoc!(
+        "
+        SELECT brand_canonical_brands.brand_id, canonical_brands.name
+        FROM brand_canonical_brands
+        JOIN canonical_brands ON canonical_brands.id = brand_canonical_brands.canonical_brand_id
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let brand_id_and_names = statement
+        .query_map([], |row| {
+            Ok(BrandIdAndName {
+                brand_id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<BrandIdAndName>, rusqlite::Error>>()?;
+    Ok(brand_id_and_names)
+}
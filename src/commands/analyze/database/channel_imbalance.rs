@@ -3,36 +3,101 @@ use indoc::indoc;
 
 #[derive(Debug)]
 pub struct Output {
-    pub brand_name: String,
-    pub left_channel_text: String,
-    pub right_channel_text: String,
+    pub brand_id: i64,
+    pub left_channel_id: i64,
+    pub right_channel_id: i64,
 }
 
 pub fn select(transaction: &rusqlite::Transaction) -> Result<Vec<Output>, Error> {
     let query = indoc!(
         "
-        SELECT brands.name, left_channels.text AS left_channel_text, right_channels.text AS right_channel_text
+        SELECT brands.id, channel_pairs.left_channel_id, channel_pairs.right_channel_id
+        FROM brands
+        JOIN phones ON phones.brand_id = brands.id
+        JOIN files ON files.phone_id = phones.id
+        JOIN channel_pairs ON channel_pairs.file_id = files.id
+        WHERE channel_pairs.left_channel_id IS NOT NULL
+        AND channel_pairs.right_channel_id IS NOT NULL
+        "
+    )
+    .trim_end();
+    select_outputs(transaction, query)
+}
+
+pub fn select_from_channels(transaction: &rusqlite::Transaction) -> Result<Vec<Output>, Error> {
+    let query = indoc!(
+        "
+        SELECT brands.id, left_channels.id AS left_channel_id, right_channels.id AS right_channel_id
         FROM brands
         JOIN phones ON phones.brand_id = brands.id
         JOIN files ON files.phone_id = phones.id
         JOIN channels left_channels ON left_channels.file_id = files.id
+        AND left_channels.type = 'Left'
         JOIN channels right_channels ON right_channels.file_id = left_channels.file_id
-        AND left_channels.id < right_channels.id
-        AND left_channels.idx = right_channels.idx
-        AND left_channels.text != right_channels.text
-        AND left_channels.type != right_channels.type
+        AND right_channels.idx = left_channels.idx
+        AND right_channels.type = 'Right'
+        WHERE NOT EXISTS (
+            SELECT 1
+            FROM channels
+            WHERE channels.file_id = left_channels.file_id
+            AND channels.idx = left_channels.idx
+            AND channels.type IN ('Left', 'Right')
+            AND channels.id NOT IN (left_channels.id, right_channels.id)
+        )
         "
     )
     .trim_end();
+    select_outputs(transaction, query)
+}
+
+fn select_outputs(transaction: &rusqlite::Transaction, query: &str) -> Result<Vec<Output>, Error> {
     let mut statement = transaction.prepare(query)?;
     let outputs = statement
         .query_map([], |row| {
             Ok(Output {
-                brand_name: row.get(0)?,
-                left_channel_text: row.get(1)?,
-                right_channel_text: row.get(2)?,
+                brand_id: row.get(0)?,
+                left_channel_id: row.get(1)?,
+                right_channel_id: row.get(2)?,
             })
         })?
         .collect::<Result<Vec<Output>, rusqlite::Error>>()?;
     Ok(outputs)
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::schema_migrator;
+
+    #[test]
+    fn it_leaves_out_sides_with_several_channels() -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let transaction = connection.transaction()?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Raw)?;
+        transaction.execute_batch(indoc!(
+            "
+            INSERT INTO brands (id, name) VALUES (1, 'Moondrop');
+            INSERT INTO phones (id, brand_id, name) VALUES (1, 1, 'Aria');
+            INSERT INTO files (id, phone_id, text) VALUES (1, 1, 'Aria');
+            INSERT INTO channels (id, content_hash, file_id, idx, type)
+            VALUES
+                (1, '1', 1, 0, 'Left'),
+                (2, '2', 1, 0, 'Right'),
+                (3, '3', 1, 1, 'Left'),
+                (4, '4', 1, 1, 'Left'),
+                (5, '5', 1, 1, 'Right');
+            "
+        ))?;
+
+        let outputs = select_from_channels(&transaction)?;
+
+        assert_eq!(
+            outputs
+                .iter()
+                .map(|output| (output.left_channel_id, output.right_channel_id))
+                .collect::<Vec<(i64, i64)>>(),
+            vec![(1, 2)]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,27 @@
+use anyhow::Error;
+use indoc::indoc;
+
+use crate::text_compressor;
+
+pub fn select_text(transaction: &rusqlite::Transaction, id: i64) -> Result<String, Error> {
+    let query = indoc!(
+        "
+        SELECT channels.text, blobs.data
+        FROM channels
+        LEFT JOIN blobs ON blobs.id = channels.blob_id
+        WHERE channels.id = ?
+        "
+    )
+    .trim_end();
+    let (text, blob) = transaction.query_row(query, [id], |row| Ok((row.get(0)?, row.get(1)?)))?;
+    text_compressor::text(text, blob)
+}
+
+pub fn select_text_groups(transaction: &rusqlite::Transaction) -> Result<Vec<(i64, i64)>, Error> {
+    let query = "SELECT id, MIN(id) OVER (PARTITION BY content_hash) FROM channels";
+    let mut statement = transaction.prepare(query)?;
+    let result = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(i64, i64)>, rusqlite::Error>>()?;
+    Ok(result)
+}
@@ -0,0 +1,10 @@
+use anyhow::Error;
+
+pub fn select(transaction: &rusqlite::Transaction) -> Result<Vec<(i64, i64)>, Error> {
+    let query = "SELECT channel_id, group_id FROM measurement_groups";
+    let mut statement = transaction.prepare(query)?;
+    let result = statement
+        .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+        .collect::<Result<Vec<(i64, i64)>, rusqlite::Error>>()?;
+    Ok(result)
+}
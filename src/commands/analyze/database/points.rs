@@ -0,0 +1,55 @@
+use anyhow::Error;
+use indoc::indoc;
+use rusqlite::OptionalExtension;
+
+use crate::measurement_parser::Point;
+
+pub fn select(transaction: &rusqlite::Transaction, channel_id: i64) -> Result<Vec<Point>, Error> {
+    if let Some(points) = select_point_array(transaction, channel_id)? {
+        return Ok(points);
+    }
+    let query = indoc!(
+        "
+        SELECT frequency_hz, phase_degrees, spl_db
+        FROM points
+        WHERE channel_id = ?
+        ORDER BY idx
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare_cached(query)?;
+    let points = statement
+        .query_map([channel_id], |row| {
+            Ok(Point {
+                frequency_hz: row.get(0)?,
+                phase_degrees: row.get(1)?,
+                spl_db: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<Point>, rusqlite::Error>>()?;
+    Ok(points)
+}
+
+fn select_point_array(
+    transaction: &rusqlite::Transaction,
+    channel_id: i64,
+) -> Result<Option<Vec<Point>>, Error> {
+    let query = "SELECT points FROM point_arrays WHERE channel_id = ?";
+    let Some(points) = transaction
+        .prepare_cached(query)?
+        .query_row([channel_id], |row| row.get::<_, String>(0))
+        .optional()?
+    else {
+        return Ok(None);
+    };
+    let points: Vec<[Option<f64>; 4]> = serde_json::from_str(&points)?;
+    let points = points
+        .into_iter()
+        .map(|[frequency_hz, phase_degrees, _, spl_db]| Point {
+            frequency_hz: frequency_hz.unwrap_or(f64::NAN),
+            phase_degrees,
+            spl_db: spl_db.unwrap_or(f64::NAN),
+        })
+        .collect();
+    Ok(Some(points))
+}
@@ -0,0 +1,30 @@
+use anyhow::Error;
+use indoc::indoc;
+
+use crate::measurement_resampler::ResampledPoint;
+
+pub fn select(
+    transaction: &rusqlite::Transaction,
+    channel_id: i64,
+) -> Result<Vec<ResampledPoint>, Error> {
+    let query = indoc!(
+        "
+        SELECT frequency_hz, idx, spl_db
+        FROM resampled_points
+        WHERE channel_id = ?
+        ORDER BY idx
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare_cached(query)?;
+    let points = statement
+        .query_map([channel_id], |row| {
+            Ok(ResampledPoint {
+                frequency_hz: row.get(0)?,
+                idx: row.get(1)?,
+                spl_db: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<ResampledPoint>, rusqlite::Error>>()?;
+    Ok(points)
+}
@@ -0,0 +1,7 @@
+use anyhow::Error;
+
+pub fn exists(transaction: &rusqlite::Transaction, name: &str) -> Result<bool, Error> {
+    let query = "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)";
+    let result = transaction.query_row(query, [name], |row| row.get(0))?;
+    Ok(result)
+}
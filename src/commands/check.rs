@@ -0,0 +1,139 @@
+use anyhow::{bail, Error};
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
+
+use crate::commands::schema_migrator;
+use crate::measurement_parser;
+
+mod database;
+
+#[derive(clap::Parser, Debug)]
+pub struct Command {
+    /// Set the input file
+    #[arg(default_value = "squig.sqlite3", long, short)]
+    input: String,
+
+    /// Set the maximum number of examples to show per problem
+    #[arg(default_value_t = 5, long)]
+    examples: usize,
+}
+
+#[derive(Clone, Copy, Debug, PartialEq)]
+enum Severity {
+    Error,
+    Warning,
+}
+
+#[derive(Debug)]
+struct Problem {
+    category: String,
+    severity: Severity,
+    examples: Vec<String>,
+}
+
+impl Command {
+    pub fn execute(&self) -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_with_flags(
+            &self.input,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+
+        let transaction = connection.transaction()?;
+        schema_migrator::ensure_latest(
+            &transaction,
+            schema_migrator::Schema::Raw,
+            "run `migrate` on it to upgrade it",
+        )?;
+        let mut problems = vec![];
+        problems.push(Problem {
+            category: "Orphan rows".to_string(),
+            severity: Severity::Error,
+            examples: database::foreign_keys::select_violations(&transaction)?
+                .iter()
+                .map(|violation| {
+                    format!(
+                        "{} {} references a missing row of {}",
+                        violation.table, violation.row_id, violation.parent
+                    )
+                })
+                .collect(),
+        });
+        if database::channels::has_column_text(&transaction)? {
+            problems.push(Problem {
+                category: "Channels failing to parse".to_string(),
+                severity: Severity::Error,
+                examples: database::channels::select_texts(&transaction)?
+                    .into_par_iter()
+                    .filter_map(|channel| {
+                        match channel
+                            .text
+                            .and_then(|text| measurement_parser::parse(&text))
+                        {
+                            Ok(_) => None,
+                            Err(err) => Some(format!(
+                                "channel {} ({}): {}",
+                                channel.id, channel.description, err
+                            )),
+                        }
+                    })
+                    .collect(),
+            });
+        } else {
+            println!(
+                "Skipped parsing the channels, the database was transformed without their text"
+            );
+        }
+        problems.push(Problem {
+            category: "Phones without files".to_string(),
+            severity: Severity::Warning,
+            examples: database::phones::select_without_files(&transaction)?,
+        });
+        problems.push(Problem {
+            category: "Files without channels".to_string(),
+            severity: Severity::Warning,
+            examples: database::files::select_without_channels(&transaction)?,
+        });
+        problems.push(Problem {
+            category: "Samples with only one of their left and right channels".to_string(),
+            severity: Severity::Warning,
+            examples: database::channels::select_unpaired(&transaction)?,
+        });
+        problems.push(Problem {
+            category: "Brands of a site differing only by case".to_string(),
+            severity: Severity::Warning,
+            examples: database::brands::select_case_duplicates(&transaction)?,
+        });
+        transaction.commit()?;
+
+        for problem in &problems {
+            println!(
+                "{} {}: {}",
+                match (problem.examples.is_empty(), problem.severity) {
+                    (true, _) => "[ok]     ",
+                    (false, Severity::Error) => "[error]  ",
+                    (false, Severity::Warning) => "[warning]",
+                },
+                problem.category,
+                problem.examples.len()
+            );
+            for example in problem.examples.iter().take(self.examples) {
+                println!("              {}", example);
+            }
+            if problem.examples.len() > self.examples {
+                println!(
+                    "              and {} more",
+                    problem.examples.len() - self.examples
+                );
+            }
+        }
+        let error_count: usize = problems
+            .iter()
+            .filter(|problem| problem.severity == Severity::Error)
+            .map(|problem| problem.examples.len())
+            .sum();
+        if error_count > 0 {
+            bail!("found {} errors in `{}`", error_count, self.input);
+        }
+
+        Ok(())
+    }
+}
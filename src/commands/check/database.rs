@@ -0,0 +1,5 @@
+pub mod brands;
+pub mod channels;
+pub mod files;
+pub mod foreign_keys;
+pub mod phones;
@@ -0,0 +1,21 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn select_case_duplicates(transaction: &rusqlite::Transaction) -> Result<Vec<String>, Error> {
+    let query = indoc!(
+        "
+        SELECT GROUP_CONCAT(brands.name, ', ') || ' (' || sites.username || '.squig.link)'
+        FROM brands
+        JOIN sites ON sites.id = brands.site_id
+        GROUP BY brands.site_id, LOWER(brands.name)
+        HAVING COUNT(*) > 1
+        ORDER BY MIN(brands.id)
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let brands = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+    Ok(brands)
+}
@@ -0,0 +1,75 @@
+use anyhow::Error;
+use indoc::indoc;
+
+use crate::text_compressor;
+
+#[derive(Debug)]
+pub struct Text {
+    pub id: i64,
+    pub description: String,
+    pub text: Result<String, Error>,
+}
+
+pub fn has_column_text(transaction: &rusqlite::Transaction) -> Result<bool, Error> {
+    let query = "SELECT EXISTS(SELECT 1 FROM pragma_table_info('channels') WHERE name = 'text')";
+    let result = transaction.query_row(query, [], |row| row.get(0))?;
+    Ok(result)
+}
+
+pub fn select_unpaired(transaction: &rusqlite::Transaction) -> Result<Vec<String>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            brands.name || ' ' || phones.name || ' (' || sites.username || '.squig.link): '
+            || files.text || ', sample ' || channels.idx || ', '
+            || MIN(channels.type) || ' without ' ||
+            CASE MIN(channels.type) WHEN 'Left' THEN 'Right' ELSE 'Left' END
+        FROM channels
+        JOIN files ON files.id = channels.file_id
+        JOIN phones ON phones.id = files.phone_id
+        JOIN brands ON brands.id = phones.brand_id
+        JOIN sites ON sites.id = brands.site_id
+        WHERE channels.type IN ('Left', 'Right')
+        GROUP BY channels.file_id, channels.idx
+        HAVING COUNT(DISTINCT channels.type) = 1
+        ORDER BY channels.file_id, channels.idx
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let samples = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+    Ok(samples)
+}
+
+pub fn select_texts(transaction: &rusqlite::Transaction) -> Result<Vec<Text>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            channels.id,
+            COALESCE(brands.name || ' ' || phones.name || ': ' || files.text, 'no file') || ', '
+            || COALESCE(channels.type, 'Mono') || ' ' || channels.idx,
+            channels.text,
+            blobs.data
+        FROM channels
+        LEFT JOIN blobs ON blobs.id = channels.blob_id
+        LEFT JOIN files ON files.id = channels.file_id
+        LEFT JOIN phones ON phones.id = files.phone_id
+        LEFT JOIN brands ON brands.id = phones.brand_id
+        ORDER BY channels.id
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let texts = statement
+        .query_map([], |row| {
+            Ok(Text {
+                id: row.get(0)?,
+                description: row.get(1)?,
+                text: text_compressor::text(row.get(2)?, row.get(3)?),
+            })
+        })?
+        .collect::<Result<Vec<Text>, rusqlite::Error>>()?;
+    Ok(texts)
+}
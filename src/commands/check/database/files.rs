@@ -0,0 +1,24 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn select_without_channels(transaction: &rusqlite::Transaction) -> Result<Vec<String>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            brands.name || ' ' || phones.name || ' (' || sites.username || '.squig.link): '
+            || files.text
+        FROM files
+        JOIN phones ON phones.id = files.phone_id
+        JOIN brands ON brands.id = phones.brand_id
+        JOIN sites ON sites.id = brands.site_id
+        WHERE NOT EXISTS (SELECT 1 FROM channels WHERE channels.file_id = files.id)
+        ORDER BY files.id
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let files = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+    Ok(files)
+}
@@ -0,0 +1,31 @@
+use anyhow::Error;
+use indoc::indoc;
+
+#[derive(Debug)]
+pub struct Violation {
+    pub table: String,
+    pub row_id: i64,
+    pub parent: String,
+}
+
+pub fn select_violations(transaction: &rusqlite::Transaction) -> Result<Vec<Violation>, Error> {
+    let query = indoc!(
+        "
+        SELECT \"table\", rowid, parent
+        FROM pragma_foreign_key_check
+        ORDER BY \"table\", parent, rowid
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let violations = statement
+        .query_map([], |row| {
+            Ok(Violation {
+                table: row.get(0)?,
+                row_id: row.get(1)?,
+                parent: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<Violation>, rusqlite::Error>>()?;
+    Ok(violations)
+}
@@ -0,0 +1,21 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn select_without_files(transaction: &rusqlite::Transaction) -> Result<Vec<String>, Error> {
+    let query = indoc!(
+        "
+        SELECT brands.name || ' ' || phones.name || ' (' || sites.username || '.squig.link)'
+        FROM phones
+        JOIN brands ON brands.id = phones.brand_id
+        JOIN sites ON sites.id = brands.site_id
+        WHERE NOT EXISTS (SELECT 1 FROM files WHERE files.phone_id = phones.id)
+        ORDER BY phones.id
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let phones = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+    Ok(phones)
+}
@@ -1,13 +1,25 @@
 use anyhow::{Context, Error};
 
+use crate::commands::run_recorder;
+use crate::commands::schema_migrator;
+use crate::hasher;
+use crate::progress_bar;
+use crate::text_compressor;
+
 mod database;
 mod requests;
 
+pub use database::migrations::MIGRATIONS;
+
 #[derive(clap::Parser, Debug)]
 pub struct Command {
     /// Set the output file
     #[arg(default_value = "squig.sqlite3", long, short)]
     output: String,
+
+    /// Store channel texts zstd-compressed in `blobs`, sharing one blob between identical texts
+    #[arg(long)]
+    compress: bool,
 }
 
 impl Command {
@@ -16,13 +28,8 @@ impl Command {
 
         let transaction = connection.transaction()?;
         database::pragmas::enable_foreign_keys(&transaction)?;
-        database::sites::create(&transaction)?;
-        database::dbs::create(&transaction)?;
-        database::brands::create(&transaction)?;
-        database::phones::create(&transaction)?;
-        database::files::create(&transaction)?;
-        database::channels::create(&transaction)?;
-        database::suffixes::create(&transaction)?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Raw)?;
+        let run_id = run_recorder::start(&transaction, "download")?;
         transaction.commit()?;
 
         let transaction = connection.transaction()?;
@@ -53,7 +60,8 @@ impl Command {
             .max()
             .unwrap_or(5);
         let squigs_progress_bar =
-            new_progress_bar(squigs.len(), max_title_len, &multi_progress, "Total")?;
+            progress_bar::new(squigs.len(), max_title_len, &multi_progress, "Total")?;
+        let mut error_count = 0;
 
         for squig in squigs {
             let transaction = connection.transaction()?;
@@ -64,10 +72,11 @@ impl Command {
                         "\nFailed to fetch data from `https://{}.squig.link{}data/phone_book.json`:\n\n    {}\n\n",
                         squig.username, squig.folder, err
                     ));
+                    error_count += 1;
                     continue;
                 }
             };
-            let progress_bar = new_progress_bar(
+            let progress_bar = progress_bar::new(
                 brands.iter().map(|brand| brand.phones.len()).sum(),
                 max_title_len,
                 &multi_progress,
@@ -124,8 +133,8 @@ impl Command {
                         let file_id = database::files::select(&transaction, phone_id, &text)?;
                         request_and_insert_zero_channel(
                             &transaction,
-                            &squig.username,
-                            &squig.folder,
+                            self.compress,
+                            &squig,
                             &text,
                             None,
                             file_id,
@@ -133,8 +142,8 @@ impl Command {
                         )?;
                         request_and_insert_zero_channel(
                             &transaction,
-                            &squig.username,
-                            &squig.folder,
+                            self.compress,
+                            &squig,
                             &text,
                             Some("L"),
                             file_id,
@@ -142,8 +151,8 @@ impl Command {
                         )?;
                         request_and_insert_other_channels(
                             &transaction,
-                            &squig.username,
-                            &squig.folder,
+                            self.compress,
+                            &squig,
                             &text,
                             "L",
                             file_id,
@@ -151,8 +160,8 @@ impl Command {
                         )?;
                         request_and_insert_zero_channel(
                             &transaction,
-                            &squig.username,
-                            &squig.folder,
+                            self.compress,
+                            &squig,
                             &text,
                             Some("R"),
                             file_id,
@@ -160,8 +169,8 @@ impl Command {
                         )?;
                         request_and_insert_other_channels(
                             &transaction,
-                            &squig.username,
-                            &squig.folder,
+                            self.compress,
+                            &squig,
                             &text,
                             "R",
                             file_id,
@@ -183,35 +192,57 @@ impl Command {
             transaction.commit()?;
         }
 
+        let transaction = connection.transaction()?;
+        run_recorder::finish(&transaction, run_id, error_count)?;
+        transaction.commit()?;
+
         Ok(())
     }
 }
 
-fn new_progress_bar(
-    len: usize,
-    max_title_len: usize,
-    multi_progress: &indicatif::MultiProgress,
-    title: &str,
-) -> Result<indicatif::ProgressBar, Error> {
-    let progress_bar = multi_progress
-        .insert_from_back(1, indicatif::ProgressBar::new(len.try_into()?))
-        .with_finish(indicatif::ProgressFinish::Abandon);
-    progress_bar.enable_steady_tick(std::time::Duration::from_secs_f64(0.1));
-    progress_bar.set_style(
-        indicatif::ProgressStyle::with_template(&format!(
-            "({{pos:>4}}/{{len:>4}}) ({{percent:>3}}%) {{msg:{}}} {{elapsed_precise}} [{{wide_bar}}]",
-            max_title_len
-        ))?
-        .progress_chars("#-"),
-    );
-    progress_bar.set_message(title.to_string());
-    Ok(progress_bar)
+fn insert_channel(
+    transaction: &rusqlite::Transaction,
+    compress: bool,
+    file_id: i64,
+    idx: i64,
+    text: &str,
+    type_: Option<&str>,
+) -> Result<(), Error> {
+    let content_hash = hasher::sha256_hex(text.as_bytes());
+    if compress {
+        database::blobs::insert(
+            transaction,
+            &text_compressor::compress(text)?,
+            &content_hash,
+        )?;
+        let blob_id = database::blobs::select(transaction, &content_hash)?;
+        database::channels::insert(
+            transaction,
+            Some(blob_id),
+            &content_hash,
+            file_id,
+            idx,
+            None,
+            type_,
+        )?;
+    } else {
+        database::channels::insert(
+            transaction,
+            None,
+            &content_hash,
+            file_id,
+            idx,
+            Some(text),
+            type_,
+        )?;
+    }
+    Ok(())
 }
 
 fn request_and_insert_zero_channel(
     transaction: &rusqlite::Transaction,
-    username: &str,
-    folder: &str,
+    compress: bool,
+    squig: &database::squig::Squig,
     text: &str,
     request_channel: Option<&str>,
     file_id: i64,
@@ -221,22 +252,22 @@ fn request_and_insert_zero_channel(
         return Ok(());
     }
     if let Ok(text) = requests::channels::call(
-        username,
-        folder,
+        &squig.username,
+        &squig.folder,
         &match request_channel {
             Some(value) => format!("{} {}.txt", text, value),
             None => format!("{}.txt", text),
         },
     ) {
-        database::channels::insert(transaction, file_id, 0, &text, database_channel)?;
+        insert_channel(transaction, compress, file_id, 0, &text, database_channel)?;
     }
     Ok(())
 }
 
 fn request_and_insert_other_channels(
     transaction: &rusqlite::Transaction,
-    username: &str,
-    folder: &str,
+    compress: bool,
+    squig: &database::squig::Squig,
     text: &str,
     request_channel: &str,
     file_id: i64,
@@ -247,11 +278,18 @@ fn request_and_insert_other_channels(
         idx += 1;
     }
     while let Ok(text) = requests::channels::call(
-        username,
-        folder,
+        &squig.username,
+        &squig.folder,
         &format!("{} {}{}.txt", text, request_channel, idx),
     ) {
-        database::channels::insert(transaction, file_id, idx, &text, Some(database_channel))?;
+        insert_channel(
+            transaction,
+            compress,
+            file_id,
+            idx,
+            &text,
+            Some(database_channel),
+        )?;
         idx += 1;
     }
     Ok(())
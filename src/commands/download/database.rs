@@ -1,7 +1,9 @@
+pub mod blobs;
 pub mod brands;
 pub mod channels;
 pub mod dbs;
 pub mod files;
+pub mod migrations;
 pub mod phones;
 pub mod pragmas;
 pub mod sites;
@@ -0,0 +1,37 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS blobs (
+            id   INTEGER PRIMARY KEY,
+            data BLOB NOT NULL,
+            hash TEXT NOT NULL,
+            UNIQUE(hash)
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn insert(transaction: &rusqlite::Transaction, data: &[u8], hash: &str) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT OR IGNORE INTO blobs (data, hash)
+        VALUES (?, ?)
+        "
+    )
+    .trim_end();
+    let params = (data, hash);
+    transaction.execute(query, params)?;
+    Ok(())
+}
+
+pub fn select(transaction: &rusqlite::Transaction, hash: &str) -> Result<i64, Error> {
+    let query = "SELECT id FROM blobs WHERE hash = ?";
+    let result = transaction.query_row(query, [hash], |row| row.get(0))?;
+    Ok(result)
+}
@@ -1,6 +1,8 @@
 use anyhow::Error;
 use indoc::indoc;
 
+use crate::hasher;
+
 pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
     let query = indoc!(
         "
@@ -25,21 +27,89 @@ pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
     Ok(())
 }
 
+pub fn add_columns_blob_id_and_content_hash(
+    transaction: &rusqlite::Transaction,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        ALTER TABLE channels
+        RENAME TO old_channels;
+
+        CREATE TABLE channels (
+            id           INTEGER PRIMARY KEY,
+            blob_id      INTEGER REFERENCES blobs(id),
+            content_hash TEXT NOT NULL,
+            file_id      INTEGER REFERENCES files(id),
+            idx          INTEGER NOT NULL,
+            text         TEXT,
+            type         TEXT,
+            UNIQUE(file_id, content_hash, type)
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+
+    let query = "SELECT id, file_id, idx, text, type FROM old_channels";
+    let mut statement = transaction.prepare(query)?;
+    let mut rows = statement.query([])?;
+    while let Some(row) = rows.next()? {
+        let text: String = row.get(3)?;
+        let query = indoc!(
+            "
+            INSERT INTO channels (id, content_hash, file_id, idx, text, type)
+            VALUES (?, ?, ?, ?, ?, ?)
+            "
+        )
+        .trim_end();
+        let params = (
+            row.get::<_, i64>(0)?,
+            hasher::sha256_hex(text.as_bytes()),
+            row.get::<_, i64>(1)?,
+            row.get::<_, i64>(2)?,
+            &text,
+            row.get::<_, Option<String>>(4)?,
+        );
+        transaction.prepare_cached(query)?.execute(params)?;
+    }
+
+    let query = indoc!(
+        "
+        DROP TABLE old_channels;
+
+        CREATE INDEX IF NOT EXISTS channels_blob_id_idx
+        ON channels(blob_id);
+        CREATE INDEX IF NOT EXISTS channels_file_id_idx
+        ON channels(file_id);
+        CREATE INDEX IF NOT EXISTS channels_idx_idx
+        ON channels(idx);
+        CREATE INDEX IF NOT EXISTS channels_type_idx
+        ON channels(type);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
 pub fn insert(
     transaction: &rusqlite::Transaction,
+    blob_id: Option<i64>,
+    content_hash: &str,
     file_id: i64,
     idx: i64,
-    text: &str,
+    text: Option<&str>,
     type_: Option<&str>,
 ) -> Result<(), Error> {
     let query = indoc!(
         "
-        INSERT OR IGNORE INTO channels (file_id, idx, text, type)
-        VALUES (?, ?, ?, ?)
+        INSERT OR IGNORE INTO channels (blob_id, content_hash, file_id, idx, text, type)
+        VALUES (?, ?, ?, ?, ?, ?)
         "
     )
     .trim_end();
-    transaction.execute(query, (file_id, idx, text, type_))?;
+    let params = (blob_id, content_hash, file_id, idx, text, type_);
+    transaction.execute(query, params)?;
     Ok(())
 }
 
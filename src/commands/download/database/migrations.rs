@@ -0,0 +1,29 @@
+use anyhow::Error;
+
+use super::{blobs, brands, channels, dbs, files, phones, sites, suffixes};
+use crate::commands::run_recorder;
+use crate::commands::schema_migrator::Migration;
+
+pub const MIGRATIONS: &[Migration] = &[create_tables, add_blobs, add_runs];
+
+fn create_tables(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    sites::create(transaction)?;
+    dbs::create(transaction)?;
+    brands::create(transaction)?;
+    phones::create(transaction)?;
+    files::create(transaction)?;
+    channels::create(transaction)?;
+    suffixes::create(transaction)?;
+    Ok(())
+}
+
+fn add_blobs(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    blobs::create(transaction)?;
+    channels::add_columns_blob_id_and_content_hash(transaction)?;
+    Ok(())
+}
+
+fn add_runs(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    run_recorder::create(transaction)?;
+    Ok(())
+}
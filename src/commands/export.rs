@@ -0,0 +1,111 @@
+use anyhow::{ensure, Error};
+use std::collections;
+use std::fs;
+use std::path::Path;
+
+use crate::commands::schema_migrator;
+use crate::measurement_parser;
+use crate::measurement_writer;
+
+mod database;
+
+#[derive(clap::Parser, Debug)]
+pub struct Command {
+    /// Set the input file
+    #[arg(default_value = "squig_transformed.sqlite3", long, short)]
+    input: String,
+
+    /// Set the output directory
+    #[arg(default_value = "export", long, short)]
+    output: String,
+
+    /// Set the averaged curves to export
+    #[arg(default_value = "phone", long, value_enum)]
+    curves: Curves,
+
+    /// Set the format of the exported files
+    #[arg(default_value = "rew-v5", long, value_enum)]
+    format: Format,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum Curves {
+    /// One curve per file, averaged across its samples
+    File,
+    /// One curve per phone, averaged across its files
+    Phone,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum Format {
+    /// Comma-separated values
+    Csv,
+    /// REW V5 text
+    RewV5,
+    /// Tab-separated values
+    Tab,
+}
+
+impl Command {
+    pub fn execute(&self) -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_with_flags(
+            &self.input,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+        fs::create_dir_all(&self.output)?;
+
+        let transaction = connection.transaction()?;
+        schema_migrator::ensure_latest(
+            &transaction,
+            schema_migrator::Schema::Transformed,
+            "transform the raw database again",
+        )?;
+        let curve_points = match self.curves {
+            Curves::File => database::average_points::select_files(&transaction)?,
+            Curves::Phone => database::average_points::select_phones(&transaction)?,
+        };
+        transaction.commit()?;
+
+        let (format, extension) = match self.format {
+            Format::Csv => (measurement_writer::Format::Csv, "csv"),
+            Format::RewV5 => (measurement_writer::Format::RewV5, "txt"),
+            Format::Tab => (measurement_writer::Format::Tab, "txt"),
+        };
+        let mut file_names = collections::HashSet::new();
+        for curve in curve_points.chunk_by(|a, b| a.curve_id == b.curve_id) {
+            let points: Vec<measurement_parser::Point> = curve
+                .iter()
+                .map(|curve_point| measurement_parser::Point {
+                    frequency_hz: curve_point.frequency_hz,
+                    phase_degrees: None,
+                    spl_db: curve_point.spl_db,
+                })
+                .collect();
+            let name = file_name(&curve[0].name);
+            let mut path = format!("{}.{}", name, extension);
+            if !file_names.insert(path.to_lowercase()) {
+                path = format!("{} ({}).{}", name, curve[0].curve_id, extension);
+                ensure!(
+                    file_names.insert(path.to_lowercase()),
+                    "found several curves exported as `{}`",
+                    path
+                );
+            }
+            fs::write(
+                Path::new(&self.output).join(path),
+                measurement_writer::write(&points, format)?,
+            )?;
+        }
+
+        Ok(())
+    }
+}
+
+fn file_name(name: &str) -> String {
+    name.chars()
+        .map(|c| match c {
+            '/' | '\\' | ':' | '*' | '?' | '"' | '<' | '>' | '|' => '_',
+            c => c,
+        })
+        .collect()
+}
@@ -0,0 +1 @@
+pub mod average_points;
@@ -0,0 +1,60 @@
+use anyhow::Error;
+use indoc::indoc;
+
+#[derive(Debug)]
+pub struct CurvePoint {
+    pub curve_id: i64,
+    pub frequency_hz: f64,
+    pub name: String,
+    pub spl_db: f64,
+}
+
+pub fn select_files(transaction: &rusqlite::Transaction) -> Result<Vec<CurvePoint>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            file_average_points.file_id,
+            file_average_points.frequency_hz,
+            v_phones.site_username || ' ' || v_phones.brand || ' ' || files.text,
+            file_average_points.spl_db
+        FROM file_average_points
+        JOIN files ON files.id = file_average_points.file_id
+        JOIN v_phones ON v_phones.phone_id = files.phone_id
+        ORDER BY file_average_points.file_id, file_average_points.idx
+        "
+    )
+    .trim_end();
+    select(transaction, query)
+}
+
+pub fn select_phones(transaction: &rusqlite::Transaction) -> Result<Vec<CurvePoint>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            phone_average_points.phone_id,
+            phone_average_points.frequency_hz,
+            v_phones.site_username || ' ' || v_phones.brand || ' ' || v_phones.phone,
+            phone_average_points.spl_db
+        FROM phone_average_points
+        JOIN v_phones ON v_phones.phone_id = phone_average_points.phone_id
+        ORDER BY phone_average_points.phone_id, phone_average_points.idx
+        "
+    )
+    .trim_end();
+    select(transaction, query)
+}
+
+fn select(transaction: &rusqlite::Transaction, query: &str) -> Result<Vec<CurvePoint>, Error> {
+    let mut statement = transaction.prepare(query)?;
+    let curve_points = statement
+        .query_map([], |row| {
+            Ok(CurvePoint {
+                curve_id: row.get(0)?,
+                frequency_hz: row.get(1)?,
+                name: row.get(2)?,
+                spl_db: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<CurvePoint>, rusqlite::Error>>()?;
+    Ok(curve_points)
+}
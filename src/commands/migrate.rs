@@ -0,0 +1,39 @@
+use anyhow::{bail, ensure, Error};
+
+use crate::commands::schema_migrator;
+
+#[derive(clap::Parser, Debug)]
+pub struct Command {
+    /// Set the input file
+    #[arg(default_value = "squig.sqlite3", long, short)]
+    input: String,
+}
+
+impl Command {
+    pub fn execute(&self) -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open(&self.input)?;
+
+        let transaction = connection.transaction()?;
+        ensure!(
+            schema_migrator::version(&transaction, schema_migrator::Schema::Transformed)?.is_none(),
+            "the input is transformed, migrate the raw database and transform it again"
+        );
+        let Some(version) = schema_migrator::version(&transaction, schema_migrator::Schema::Raw)?
+        else {
+            bail!("the database has no raw schema");
+        };
+        let latest_version = schema_migrator::Schema::Raw.latest_version();
+        if version == latest_version {
+            println!("The raw schema is already at version {}", version);
+            return Ok(());
+        }
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Raw)?;
+        transaction.commit()?;
+
+        println!(
+            "Migrated the raw schema from version {} to {}",
+            version, latest_version
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,81 @@
+use anyhow::Error;
+use std::env;
+
+mod database;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    database::runs::create(transaction)?;
+    database::run_row_counts::create(transaction)?;
+    Ok(())
+}
+
+pub fn start(transaction: &rusqlite::Transaction, command: &str) -> Result<i64, Error> {
+    let arguments: Vec<String> = env::args().skip(1).collect();
+    let run_id = database::runs::insert(
+        transaction,
+        &serde_json::to_string(&arguments)?,
+        command,
+        env!("CARGO_PKG_VERSION"),
+    )?;
+    Ok(run_id)
+}
+
+pub fn finish(
+    transaction: &rusqlite::Transaction,
+    run_id: i64,
+    error_count: i64,
+) -> Result<(), Error> {
+    database::runs::update_end(transaction, run_id, error_count)?;
+    let row_counts = database::tables::select_names(transaction)?
+        .into_iter()
+        .map(|table_name| {
+            let row_count = database::tables::count_rows(transaction, &table_name)?;
+            Ok((table_name, row_count))
+        })
+        .collect::<Result<Vec<(String, i64)>, Error>>()?;
+    for (table_name, row_count) in row_counts {
+        database::run_row_counts::insert(transaction, run_id, row_count, &table_name)?;
+    }
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_records_a_run() -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let transaction = connection.transaction()?;
+        create(&transaction)?;
+        transaction.execute_batch("CREATE TABLE sites (id INTEGER PRIMARY KEY)")?;
+        transaction.execute_batch("INSERT INTO sites DEFAULT VALUES")?;
+
+        let run_id = start(&transaction, "download")?;
+        finish(&transaction, run_id, 2)?;
+
+        let (command, error_count, version): (String, i64, String) = transaction.query_row(
+            "SELECT command, error_count, version FROM runs WHERE ended_at >= started_at",
+            [],
+            |row| Ok((row.get(0)?, row.get(1)?, row.get(2)?)),
+        )?;
+        assert_eq!(
+            (command.as_str(), error_count, version.as_str()),
+            ("download", 2, env!("CARGO_PKG_VERSION"))
+        );
+        let mut statement = transaction
+            .prepare("SELECT table_name, row_count FROM run_row_counts ORDER BY table_name")?;
+        let row_counts = statement
+            .query_map([], |row| Ok((row.get(0)?, row.get(1)?)))?
+            .collect::<Result<Vec<(String, i64)>, rusqlite::Error>>()?;
+        assert_eq!(
+            row_counts,
+            vec![
+                ("run_row_counts".to_string(), 0),
+                ("runs".to_string(), 1),
+                ("sites".to_string(), 1)
+            ]
+        );
+        Ok(())
+    }
+}
@@ -0,0 +1,3 @@
+pub mod run_row_counts;
+pub mod runs;
+pub mod tables;
@@ -0,0 +1,37 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS run_row_counts (
+            id         INTEGER PRIMARY KEY,
+            run_id     INTEGER NOT NULL REFERENCES runs(id),
+            row_count  INTEGER NOT NULL,
+            table_name TEXT NOT NULL,
+            UNIQUE(run_id, table_name)
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    run_id: i64,
+    row_count: i64,
+    table_name: &str,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO run_row_counts (run_id, row_count, table_name)
+        VALUES (?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (run_id, row_count, table_name);
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
@@ -0,0 +1,57 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS runs (
+            id          INTEGER PRIMARY KEY,
+            arguments   TEXT NOT NULL,
+            command     TEXT NOT NULL,
+            ended_at    TEXT,
+            error_count INTEGER,
+            started_at  TEXT NOT NULL,
+            version     TEXT NOT NULL
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    arguments: &str,
+    command: &str,
+    version: &str,
+) -> Result<i64, Error> {
+    let query = indoc!(
+        "
+        INSERT INTO runs (arguments, command, started_at, version)
+        VALUES (?, ?, strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), ?)
+        "
+    )
+    .trim_end();
+    let params = (arguments, command, version);
+    transaction.execute(query, params)?;
+    Ok(transaction.last_insert_rowid())
+}
+
+pub fn update_end(
+    transaction: &rusqlite::Transaction,
+    id: i64,
+    error_count: i64,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        UPDATE runs
+        SET ended_at = strftime('%Y-%m-%dT%H:%M:%fZ', 'now'), error_count = ?
+        WHERE id = ?
+        "
+    )
+    .trim_end();
+    let params = (error_count, id);
+    transaction.execute(query, params)?;
+    Ok(())
+}
@@ -0,0 +1,25 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn select_names(transaction: &rusqlite::Transaction) -> Result<Vec<String>, Error> {
+    let query = indoc!(
+        "
+        SELECT name
+        FROM pragma_table_list
+        WHERE schema = 'main' AND type IN ('table', 'virtual') AND name NOT LIKE 'sqlite_%'
+        ORDER BY name
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let names = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<String>, rusqlite::Error>>()?;
+    Ok(names)
+}
+
+pub fn count_rows(transaction: &rusqlite::Transaction, name: &str) -> Result<i64, Error> {
+    let query = format!("SELECT COUNT(*) FROM \"{}\"", name.replace('"', "\"\""));
+    let row_count = transaction.query_row(&query, [], |row| row.get(0))?;
+    Ok(row_count)
+}
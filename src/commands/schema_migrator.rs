@@ -0,0 +1,189 @@
+use anyhow::{bail, ensure, Error};
+use indoc::indoc;
+use rusqlite::OptionalExtension;
+
+use crate::commands;
+
+pub type Migration = fn(&rusqlite::Transaction) -> Result<(), Error>;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Schema {
+    Raw,
+    Transformed,
+}
+
+impl Schema {
+    pub fn latest_version(self) -> i64 {
+        self.migrations().len() as i64
+    }
+
+    fn migrations(self) -> &'static [Migration] {
+        match self {
+            Self::Raw => commands::download::MIGRATIONS,
+            Self::Transformed => commands::transform::MIGRATIONS,
+        }
+    }
+
+    fn as_str(self) -> &'static str {
+        match self {
+            Self::Raw => "raw",
+            Self::Transformed => "transformed",
+        }
+    }
+
+    fn marker_table(self) -> &'static str {
+        match self {
+            Self::Raw => "sites",
+            Self::Transformed => "points",
+        }
+    }
+}
+
+pub fn version(transaction: &rusqlite::Transaction, schema: Schema) -> Result<Option<i64>, Error> {
+    if table_exists(transaction, "schema_version")? {
+        let query = "SELECT version FROM schema_version WHERE schema = ?";
+        let version = transaction
+            .query_row(query, [schema.as_str()], |row| row.get(0))
+            .optional()?;
+        if version.is_some() {
+            return Ok(version);
+        }
+    }
+    if !table_exists(transaction, schema.marker_table())? {
+        return Ok(None);
+    }
+    match schema {
+        Schema::Raw => Ok(Some(1)),
+        Schema::Transformed => bail!(
+            "the database was transformed before schema versioning, transform the raw database \
+             again"
+        ),
+    }
+}
+
+pub fn migrate(transaction: &rusqlite::Transaction, schema: Schema) -> Result<(), Error> {
+    let version = version(transaction, schema)?.unwrap_or(0);
+    ensure_not_newer(schema, version)?;
+    for migration in &schema.migrations()[usize::try_from(version)?..] {
+        migration(transaction)?;
+    }
+
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS schema_version (
+            schema  TEXT PRIMARY KEY,
+            version INTEGER NOT NULL
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    let query = indoc!(
+        "
+        INSERT OR REPLACE INTO schema_version (schema, version)
+        VALUES (?, ?)
+        "
+    )
+    .trim_end();
+    transaction.execute(query, (schema.as_str(), schema.latest_version()))?;
+    Ok(())
+}
+
+pub fn ensure_latest(
+    transaction: &rusqlite::Transaction,
+    schema: Schema,
+    upgrade: &str,
+) -> Result<(), Error> {
+    let Some(version) = version(transaction, schema)? else {
+        bail!("the database has no {} schema", schema.as_str());
+    };
+    ensure_not_newer(schema, version)?;
+    ensure!(
+        version == schema.latest_version(),
+        "the database has version {} of the {} schema instead of {}, {}",
+        version,
+        schema.as_str(),
+        schema.latest_version(),
+        upgrade
+    );
+    Ok(())
+}
+
+fn ensure_not_newer(schema: Schema, version: i64) -> Result<(), Error> {
+    ensure!(
+        version <= schema.latest_version(),
+        "the database has version {} of the {} schema, but this wobbling-statistics {} only \
+         supports up to version {}",
+        version,
+        schema.as_str(),
+        env!("CARGO_PKG_VERSION"),
+        schema.latest_version()
+    );
+    Ok(())
+}
+
+fn table_exists(transaction: &rusqlite::Transaction, name: &str) -> Result<bool, Error> {
+    let query = "SELECT EXISTS(SELECT 1 FROM sqlite_master WHERE type = 'table' AND name = ?)";
+    let result = transaction.query_row(query, [name], |row| row.get(0))?;
+    Ok(result)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_migrates_a_new_database() -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let transaction = connection.transaction()?;
+
+        migrate(&transaction, Schema::Raw)?;
+
+        assert_eq!(
+            version(&transaction, Schema::Raw)?,
+            Some(Schema::Raw.latest_version())
+        );
+        assert!(table_exists(&transaction, "sites")?);
+        Ok(())
+    }
+
+    #[test]
+    fn it_recognizes_raw_databases_from_before_versioning() -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let transaction = connection.transaction()?;
+        Schema::Raw.migrations()[0](&transaction)?;
+
+        migrate(&transaction, Schema::Raw)?;
+
+        assert_eq!(
+            version(&transaction, Schema::Raw)?,
+            Some(Schema::Raw.latest_version())
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn it_refuses_newer_databases() -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let transaction = connection.transaction()?;
+        migrate(&transaction, Schema::Raw)?;
+        transaction.execute(
+            "UPDATE schema_version SET version = ?",
+            [Schema::Raw.latest_version() + 1],
+        )?;
+
+        assert!(migrate(&transaction, Schema::Raw).is_err());
+        assert!(ensure_latest(&transaction, Schema::Raw, "").is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn it_refuses_transformed_databases_from_before_versioning() -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let transaction = connection.transaction()?;
+        transaction.execute_batch("CREATE TABLE points (id INTEGER PRIMARY KEY)")?;
+
+        assert!(version(&transaction, Schema::Transformed).is_err());
+        Ok(())
+    }
+}
@@ -0,0 +1,67 @@
+use anyhow::Error;
+
+use crate::commands::schema_migrator;
+
+mod database;
+
+#[derive(clap::Parser, Debug)]
+pub struct Command {
+    /// Set the words to look for in brand, phone, model, suffix and file names
+    #[arg(required = true)]
+    words: Vec<String>,
+
+    /// Set the input file
+    #[arg(default_value = "squig_transformed.sqlite3", long, short)]
+    input: String,
+
+    /// Set the maximum number of phones to show
+    #[arg(default_value_t = 20, long, short)]
+    limit: u32,
+}
+
+impl Command {
+    pub fn execute(&self) -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_with_flags(
+            &self.input,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+
+        let transaction = connection.transaction()?;
+        schema_migrator::ensure_latest(
+            &transaction,
+            schema_migrator::Schema::Transformed,
+            "transform the raw database again",
+        )?;
+        for phone in database::phone_search::select(&transaction, &self.query(), self.limit)? {
+            let sides = database::channels::select_sides(&transaction, phone.id)?
+                .iter()
+                .map(|side| format!("{} x{}", side.name, side.count))
+                .collect::<Vec<String>>()
+                .join(", ");
+            println!(
+                "{} {} ({}.squig.link, {}): {}",
+                phone.brand,
+                phone.name,
+                phone.site_username,
+                phone.db_types.as_deref().unwrap_or("no db"),
+                if sides.is_empty() {
+                    "no channels"
+                } else {
+                    &sides
+                }
+            );
+        }
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn query(&self) -> String {
+        self.words
+            .iter()
+            .flat_map(|words| words.split_whitespace())
+            .map(|word| format!("\"{}\"*", word.replace('"', "\"\"")))
+            .collect::<Vec<String>>()
+            .join(" ")
+    }
+}
@@ -0,0 +1,35 @@
+use anyhow::Error;
+use indoc::indoc;
+
+#[derive(Debug)]
+pub struct Side {
+    pub name: String,
+    pub count: i64,
+}
+
+pub fn select_sides(
+    transaction: &rusqlite::Transaction,
+    phone_id: i64,
+) -> Result<Vec<Side>, Error> {
+    let query = indoc!(
+        "
+        SELECT COALESCE(channels.type, 'Mono') AS side, COUNT(*)
+        FROM channels
+        JOIN files ON files.id = channels.file_id
+        WHERE files.phone_id = ?
+        GROUP BY side
+        ORDER BY side
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let sides = statement
+        .query_map([phone_id], |row| {
+            Ok(Side {
+                name: row.get(0)?,
+                count: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<Side>, rusqlite::Error>>()?;
+    Ok(sides)
+}
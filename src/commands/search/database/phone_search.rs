@@ -0,0 +1,47 @@
+use anyhow::Error;
+use indoc::indoc;
+
+#[derive(Debug)]
+pub struct Phone {
+    pub id: i64,
+    pub brand: String,
+    pub db_types: Option<String>,
+    pub name: String,
+    pub site_username: String,
+}
+
+pub fn select(
+    transaction: &rusqlite::Transaction,
+    fts_query: &str,
+    limit: u32,
+) -> Result<Vec<Phone>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            v_phones.phone_id,
+            v_phones.brand,
+            v_phones.db_types,
+            v_phones.phone,
+            v_phones.site_username
+        FROM phone_search
+        JOIN v_phones ON v_phones.phone_id = phone_search.phone_id
+        WHERE phone_search MATCH ?
+        ORDER BY phone_search.rank
+        LIMIT ?
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let phones = statement
+        .query_map((fts_query, limit), |row| {
+            Ok(Phone {
+                id: row.get(0)?,
+                brand: row.get(1)?,
+                db_types: row.get(2)?,
+                name: row.get(3)?,
+                site_username: row.get(4)?,
+            })
+        })?
+        .collect::<Result<Vec<Phone>, rusqlite::Error>>()?;
+    Ok(phones)
+}
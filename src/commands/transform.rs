@@ -1,10 +1,34 @@
-use anyhow::Error;
+use anyhow::{ensure, Context, Error};
+use clap::builder::TypedValueParser;
+use rayon::iter::{IntoParallelIterator, ParallelIterator};
 use std::fs;
+use std::path::{Path, PathBuf};
+use std::process;
+use std::sync::mpsc;
+use std::thread;
 
+use crate::commands::run_recorder;
+use crate::commands::schema_migrator;
+use crate::hasher;
+use crate::identity_resolver;
+use crate::measurement_averager;
+use crate::measurement_deduplicator;
+use crate::measurement_normalizer;
 use crate::measurement_parser;
+use crate::measurement_resampler;
+use crate::measurement_smoother;
+use crate::measurement_summarizer;
+use crate::measurement_validator;
+use crate::progress_bar;
 
 mod database;
 
+pub use database::migrations::MIGRATIONS;
+
+const CHANNEL_BUFFER_LEN: usize = 256;
+
+const MAX_GRID_LEN: u32 = 10_000;
+
 #[derive(clap::Parser, Debug)]
 pub struct Command {
     /// Set the input file
@@ -12,34 +36,799 @@ pub struct Command {
     input: String,
 
     /// Set the output file
-    #[arg(default_value = "squig_transformed.sqlite3", long, short)]
+    #[arg(
+        conflicts_with = "in_place",
+        default_value = "squig_transformed.sqlite3",
+        long,
+        short
+    )]
     output: String,
+
+    /// Set the TOML file with brand and model aliases used to resolve phone identities
+    #[arg(long)]
+    aliases: Option<String>,
+
+    /// Set the domain in which channels are averaged into sample, file and phone curves
+    #[arg(default_value = "db", long, value_enum)]
+    averaging: AveragingDomain,
+
+    /// Store the points of each channel as one JSON array in `point_arrays` instead of one row
+    /// per point in `points`, both read through the `v_points` view
+    #[arg(long)]
+    compact_points: bool,
+
+    /// Overwrite the output file when it already exists
+    #[arg(long)]
+    force: bool,
+
+    /// Transform the input file instead of a copy of it
+    #[arg(conflicts_with = "incremental", long)]
+    in_place: bool,
+
+    /// Keep the original measurement text in `channels.text`
+    #[arg(long)]
+    keep_text: bool,
+
+    /// Only parse channels added or changed since the output file was last transformed
+    #[arg(long)]
+    incremental: bool,
+
+    /// Set the level normalization strategy
+    #[arg(default_value = "none", long, value_enum)]
+    normalization: NormalizationStrategy,
+
+    /// Set the highest frequency of the normalization band
+    #[arg(default_value_t = 3_000.0, long)]
+    normalization_band_end_hz: f64,
+
+    /// Set the lowest frequency of the normalization band
+    #[arg(default_value_t = 300.0, long)]
+    normalization_band_start_hz: f64,
+
+    /// Set the frequency normalized to 0 dB
+    #[arg(default_value_t = 1_000.0, long)]
+    normalization_frequency_hz: f64,
+
+    /// Set the target file matched within the normalization band
+    #[arg(long, required_if_eq("normalization", "target"))]
+    normalization_target: Option<String>,
+
+    /// Set the fractional-octave smoothing, e.g. 12 for 1/12 octave
+    #[arg(
+        long,
+        value_parser = clap::builder::PossibleValuesParser::new(["3", "6", "12", "24", "48"])
+            .try_map(|value| value.parse::<u32>())
+    )]
+    smoothing: Option<u32>,
+
+    /// Set the lowest frequency of the resampling grid
+    #[arg(default_value_t = 20.0, long)]
+    resample_start_hz: f64,
+
+    /// Set the highest frequency of the resampling grid
+    #[arg(default_value_t = 20_000.0, long)]
+    resample_end_hz: f64,
+
+    /// Set the number of resampling grid points per octave
+    #[arg(default_value_t = 48, long, value_parser = clap::value_parser!(u32).range(1..))]
+    resample_points_per_octave: u32,
+}
+
+#[derive(Debug)]
+struct TransformedChannel {
+    id: i32,
+    impedance_points: Vec<measurement_parser::ImpedancePoint>,
+    issues: Vec<measurement_validator::Issue>,
+    metrics: measurement_summarizer::Metrics,
+    offset: Option<f64>,
+    points: Vec<measurement_parser::Point>,
+    resampled_points: Vec<measurement_resampler::ResampledPoint>,
+    smoothed_spl_db: Option<Vec<f64>>,
+}
+
+impl TransformedChannel {
+    fn empty(id: i32, issue: measurement_validator::Issue) -> Self {
+        TransformedChannel {
+            id,
+            impedance_points: vec![],
+            issues: vec![issue],
+            metrics: measurement_summarizer::Metrics::default(),
+            offset: None,
+            points: vec![],
+            resampled_points: vec![],
+            smoothed_spl_db: None,
+        }
+    }
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum AveragingDomain {
+    /// Average levels in dB
+    Db,
+    /// Average sound power
+    Power,
+}
+
+#[derive(clap::ValueEnum, Clone, Copy, Debug)]
+enum NormalizationStrategy {
+    /// Keep the absolute levels
+    None,
+    /// Offset to 0 dB at the normalization frequency
+    AtFrequency,
+    /// Offset to 0 dB on average over the normalization band
+    BandAverage,
+    /// Match the target on average over the normalization band
+    Target,
 }
 
 impl Command {
     pub fn execute(&self) -> Result<(), Error> {
-        fs::copy(&self.input, &self.output)?;
-        let mut connection = rusqlite::Connection::open(&self.output)?;
+        ensure!(
+            0.0 < self.resample_start_hz
+                && self.resample_start_hz < self.resample_end_hz
+                && self.resample_end_hz.is_finite(),
+            "the resampling grid has to start above 0 Hz and below its end frequency, which has to \
+             be finite"
+        );
+        ensure!(
+            (self.resample_end_hz / self.resample_start_hz).log2()
+                * f64::from(self.resample_points_per_octave)
+                < f64::from(MAX_GRID_LEN),
+            "the resampling grid can't have more than {} points",
+            MAX_GRID_LEN
+        );
+        self.check_input()?;
+        if self.incremental && Path::new(&self.output).exists() {
+            return self.execute_incremental();
+        }
+        let output = if self.in_place {
+            &self.input
+        } else {
+            ensure!(
+                self.force || !Path::new(&self.output).exists(),
+                "the output file `{}` already exists, pass --force to overwrite it",
+                self.output
+            );
+            &self.output
+        };
+
+        let temporary_output = temporary_path(output)?;
+        fs::copy(&self.input, &temporary_output)?;
+        if let Err(err) = self.transform(&temporary_output) {
+            let _ = fs::remove_file(&temporary_output);
+            return Err(err);
+        }
+        fs::rename(&temporary_output, output)?;
+
+        Ok(())
+    }
+
+    fn transform(&self, path: &Path) -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open(path)?;
 
         let transaction = connection.transaction()?;
+        let run_id = run_recorder::start(&transaction, "transform")?;
         let channels = database::channels::select(&transaction)?;
-        database::channels::drop_column_text(&transaction)?;
-        database::points::create(&transaction)?;
+        if self.keep_text {
+            for channel in &channels {
+                database::channels::update_text(&transaction, channel.id, &channel.text)?;
+            }
+        } else {
+            database::channels::drop_column_text(&transaction)?;
+        }
+        database::blobs::delete_all(&transaction)?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Transformed)?;
+        let normalization = self.normalization(&transaction)?;
+        let grid = self.grid();
+        database::channels::update_text_hashes(&transaction)?;
+        let mut error_count =
+            self.transform_channels(&transaction, &grid, &normalization, channels)?;
+        error_count += pair_channels(&transaction)?;
+        self.average_curves(&transaction)?;
+        self.resolve_identities(&transaction)?;
+        index_phones(&transaction)?;
+        group_measurements(&transaction)?;
+        run_recorder::finish(&transaction, run_id, error_count)?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn execute_incremental(&self) -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open(&self.output)?;
+        database::input::attach(&connection, &self.input)?;
+
+        let transaction = connection.transaction()?;
+        schema_migrator::ensure_latest(
+            &transaction,
+            schema_migrator::Schema::Raw,
+            "transform it again without --incremental",
+        )?;
+        let run_id = run_recorder::start(&transaction, "transform")?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Transformed)?;
+        ensure!(
+            self.keep_text == database::channels::has_column_text(&transaction)?,
+            "the output file was transformed {} --keep-text, transform it again without \
+             --incremental to change it",
+            if self.keep_text { "without" } else { "with" }
+        );
+        delete_derived_rows(&transaction)?;
+        for id in database::channels::select_removed_ids(&transaction)? {
+            delete_channel_rows(&transaction, id)?;
+            database::channels::delete(&transaction, id)?;
+        }
+        database::input::sync(&transaction)?;
+        let previous_options = (
+            database::normalizations::select(&transaction)?,
+            database::transform_options::select(&transaction)?,
+        );
+        let normalization = self.normalization(&transaction)?;
+        if (
+            database::normalizations::select(&transaction)?,
+            database::transform_options::select(&transaction)?,
+        ) != previous_options
+        {
+            println!("The options applied to every channel changed, parsing every channel again");
+            database::channels::clear_text_hash(&transaction)?;
+        }
+        let grid = self.grid();
+        let channels = database::channels::select_changed_input(&transaction)?;
+        let mut changed_channels = vec![];
         for channel in channels {
-            let points = measurement_parser::parse(&channel.text)?;
-            for (index, point) in points.iter().enumerate() {
-                database::points::insert(
-                    &transaction,
+            delete_channel_rows(&transaction, channel.id)?;
+            if self.keep_text {
+                database::channels::upsert_with_text(&transaction, &channel)?;
+            } else {
+                database::channels::upsert(&transaction, &channel)?;
+            }
+            database::channels::update_text_hash(&transaction, channel.id)?;
+            changed_channels.push(database::channels::IdAndText {
+                id: channel.id,
+                text: channel.text,
+            });
+        }
+        let mut error_count =
+            self.transform_channels(&transaction, &grid, &normalization, changed_channels)?;
+        error_count += pair_channels(&transaction)?;
+        self.average_curves(&transaction)?;
+        self.resolve_identities(&transaction)?;
+        index_phones(&transaction)?;
+        group_measurements(&transaction)?;
+        run_recorder::finish(&transaction, run_id, error_count)?;
+        transaction.commit()?;
+
+        Ok(())
+    }
+
+    fn check_input(&self) -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_with_flags(
+            &self.input,
+            rusqlite::OpenFlags::SQLITE_OPEN_READ_ONLY,
+        )?;
+        let transaction = connection.transaction()?;
+        schema_migrator::ensure_latest(
+            &transaction,
+            schema_migrator::Schema::Raw,
+            "run `migrate` on it to upgrade it",
+        )?;
+        ensure!(
+            schema_migrator::version(&transaction, schema_migrator::Schema::Transformed)?.is_none(),
+            "the input is already transformed, use the raw database downloaded by `download`"
+        );
+        Ok(())
+    }
+
+    fn grid(&self) -> Vec<f64> {
+        measurement_resampler::grid(
+            self.resample_start_hz,
+            self.resample_end_hz,
+            self.resample_points_per_octave,
+        )
+    }
+
+    fn transform_channels(
+        &self,
+        transaction: &rusqlite::Transaction,
+        grid: &[f64],
+        normalization: &measurement_normalizer::Normalization,
+        channels: Vec<database::channels::IdAndText>,
+    ) -> Result<i64, Error> {
+        let mut error_count = 0;
+        let progress_bar = progress_bar::new(
+            channels.len(),
+            "Channels".len(),
+            &indicatif::MultiProgress::new(),
+            "Channels",
+        )?;
+        let (sender, receiver) = mpsc::sync_channel(CHANNEL_BUFFER_LEN);
+        thread::scope(|scope| {
+            scope.spawn(move || {
+                channels
+                    .into_par_iter()
+                    .try_for_each_with(sender, |sender, channel| {
+                        sender.send(self.transform_channel(grid, normalization, channel))
+                    })
+            });
+            for transformed_channel in receiver {
+                let quality =
+                    measurement_validator::Quality::from_issues(&transformed_channel.issues);
+                if quality == measurement_validator::Quality::Error {
+                    error_count += 1;
+                }
+                database::channels::update_quality(
+                    transaction,
+                    transformed_channel.id,
+                    quality.as_str(),
+                )?;
+                for issue in &transformed_channel.issues {
+                    database::channel_issues::insert(
+                        transaction,
+                        transformed_channel.id,
+                        issue.as_str(),
+                        issue.severity().as_str(),
+                    )?;
+                }
+                database::channel_metrics::insert(
+                    transaction,
+                    transformed_channel.id,
+                    &transformed_channel.metrics,
+                )?;
+                if self.compact_points {
+                    database::point_arrays::insert(
+                        transaction,
+                        transformed_channel.id,
+                        &point_array(&transformed_channel),
+                    )?;
+                } else {
+                    for (index, point) in transformed_channel.points.iter().enumerate() {
+                        database::points::insert(
+                            transaction,
+                            transformed_channel.id,
+                            point.frequency_hz,
+                            i32::try_from(index)?,
+                            point.phase_degrees,
+                            transformed_channel
+                                .smoothed_spl_db
+                                .as_ref()
+                                .map(|values| values[index]),
+                            point.spl_db,
+                        )?;
+                    }
+                }
+                for (index, point) in transformed_channel.impedance_points.iter().enumerate() {
+                    database::impedance_points::insert(
+                        transaction,
+                        transformed_channel.id,
+                        i32::try_from(index)?,
+                        point,
+                    )?;
+                }
+                for point in &transformed_channel.resampled_points {
+                    database::resampled_points::insert(
+                        transaction,
+                        transformed_channel.id,
+                        point.frequency_hz,
+                        i32::try_from(point.idx)?,
+                        transformed_channel
+                            .offset
+                            .map(|offset| point.spl_db - offset),
+                        point.spl_db,
+                    )?;
+                }
+                progress_bar.inc(1);
+            }
+            Ok(error_count)
+        })
+    }
+
+    fn transform_channel(
+        &self,
+        grid: &[f64],
+        normalization: &measurement_normalizer::Normalization,
+        channel: database::channels::IdAndText,
+    ) -> Box<TransformedChannel> {
+        if measurement_parser::is_impedance(&channel.text) {
+            let Ok(impedance_points) = measurement_parser::parse_impedance(&channel.text) else {
+                return Box::new(TransformedChannel::empty(
                     channel.id,
-                    point.frequency_hz,
-                    i32::try_from(index)?,
-                    point.phase_degrees,
-                    point.spl_db,
+                    measurement_validator::Issue::Unparsable,
+                ));
+            };
+            return Box::new(TransformedChannel {
+                impedance_points,
+                ..TransformedChannel::empty(
+                    channel.id,
+                    measurement_validator::Issue::ImpedanceCurve,
+                )
+            });
+        }
+        let Ok(points) = measurement_parser::parse(&channel.text) else {
+            return Box::new(TransformedChannel::empty(
+                channel.id,
+                measurement_validator::Issue::Unparsable,
+            ));
+        };
+        let issues = measurement_validator::validate(&points);
+        let smoothed_spl_db = self.smoothing.map(|fraction| {
+            match measurement_parser::parse_smoothing(&channel.text) {
+                Some(existing_fraction) if existing_fraction <= fraction => {
+                    points.iter().map(|point| point.spl_db).collect()
+                }
+                _ => measurement_smoother::smooth(&points, fraction),
+            }
+        });
+        Box::new(TransformedChannel {
+            id: channel.id,
+            impedance_points: vec![],
+            issues,
+            metrics: measurement_summarizer::summarize(&points),
+            offset: measurement_normalizer::offset(&points, normalization),
+            resampled_points: measurement_resampler::resample(&points, grid),
+            smoothed_spl_db,
+            points,
+        })
+    }
+
+    fn resolve_identities(&self, transaction: &rusqlite::Transaction) -> Result<(), Error> {
+        let aliases = match &self.aliases {
+            Some(path) => identity_resolver::Aliases::load(path)?,
+            None => identity_resolver::Aliases::default(),
+        };
+        let resolution = identity_resolver::resolve(
+            &database::brands::select(transaction)?,
+            &database::phones::select(transaction)?,
+            &aliases,
+        );
+
+        database::phone_models::delete_all(transaction)?;
+        database::models::delete_all(transaction)?;
+        database::brand_canonical_brands::delete_all(transaction)?;
+        database::canonical_brands::delete_all(transaction)?;
+        for (idx, name) in resolution.brands.canonical_brands.iter().enumerate() {
+            database::canonical_brands::insert(transaction, i64::try_from(idx)? + 1, name)?;
+        }
+        for (brand_id, canonical_brand_idx) in resolution.brands.brand_canonical_brands {
+            database::brand_canonical_brands::insert(
+                transaction,
+                brand_id,
+                i64::try_from(canonical_brand_idx)? + 1,
+            )?;
+        }
+        for (idx, model) in resolution.models.iter().enumerate() {
+            database::models::insert(
+                transaction,
+                i64::try_from(idx)? + 1,
+                i64::try_from(model.canonical_brand_idx)? + 1,
+                &model.name,
+            )?;
+        }
+        for (phone_id, model_idx) in resolution.phone_models {
+            database::phone_models::insert(transaction, phone_id, i64::try_from(model_idx)? + 1)?;
+        }
+        Ok(())
+    }
+
+    fn average_curves(&self, transaction: &rusqlite::Transaction) -> Result<(), Error> {
+        let domain = match self.averaging {
+            AveragingDomain::Db => measurement_averager::Domain::Decibel,
+            AveragingDomain::Power => measurement_averager::Domain::Power,
+        };
+        database::phone_average_points::delete_all(transaction)?;
+        database::file_average_points::delete_all(transaction)?;
+        database::sample_average_points::delete_all(transaction)?;
+        let samples = database::channel_pairs::select_samples(transaction)?;
+        for phone_samples in samples.chunk_by(|a, b| a.phone_id == b.phone_id) {
+            let mut file_curves = vec![];
+            for file_samples in phone_samples.chunk_by(|a, b| a.file_id == b.file_id) {
+                let mut sample_curves = vec![];
+                for sample in file_samples {
+                    let channel_ids = match (
+                        sample.left_channel_id,
+                        sample.mono_channel_id,
+                        sample.right_channel_id,
+                    ) {
+                        (Some(left_channel_id), _, Some(right_channel_id)) => {
+                            vec![left_channel_id, right_channel_id]
+                        }
+                        (_, Some(mono_channel_id), _) => vec![mono_channel_id],
+                        (left_channel_id, None, right_channel_id) => {
+                            left_channel_id.or(right_channel_id).into_iter().collect()
+                        }
+                    };
+                    let channel_curves = channel_ids
+                        .into_iter()
+                        .map(|channel_id| {
+                            database::resampled_points::select(transaction, channel_id)
+                        })
+                        .collect::<Result<Vec<Vec<measurement_averager::AveragedPoint>>, Error>>(
+                        )?;
+                    let sample_curve = measurement_averager::average(&channel_curves, domain);
+                    for point in &sample_curve {
+                        database::sample_average_points::insert(
+                            transaction,
+                            sample.file_id,
+                            sample.idx,
+                            point,
+                        )?;
+                    }
+                    if !sample_curve.is_empty() {
+                        sample_curves.push(sample_curve);
+                    }
+                }
+                let file_curve = measurement_averager::average(&sample_curves, domain);
+                for point in &file_curve {
+                    database::file_average_points::insert(
+                        transaction,
+                        file_samples[0].file_id,
+                        point,
+                    )?;
+                }
+                if !file_curve.is_empty() {
+                    file_curves.push(file_curve);
+                }
+            }
+            for point in measurement_averager::average(&file_curves, domain) {
+                database::phone_average_points::insert(
+                    transaction,
+                    phone_samples[0].phone_id,
+                    &point,
                 )?;
             }
         }
+        Ok(())
+    }
+
+    fn normalization(
+        &self,
+        transaction: &rusqlite::Transaction,
+    ) -> Result<measurement_normalizer::Normalization, Error> {
+        let band_end_hz = self.normalization_band_end_hz;
+        let band_start_hz = self.normalization_band_start_hz;
+        let frequency_hz = self.normalization_frequency_hz;
+        ensure!(
+            0.0 < band_start_hz && band_start_hz < band_end_hz,
+            "the normalization band has to start above 0 Hz and below its end frequency"
+        );
+        let (normalization, row) = match self.normalization {
+            NormalizationStrategy::None => (
+                measurement_normalizer::Normalization::None,
+                database::normalizations::Normalization {
+                    band_end_hz: None,
+                    band_start_hz: None,
+                    frequency_hz: None,
+                    strategy: "none".to_string(),
+                    target: None,
+                    target_hash: None,
+                },
+            ),
+            NormalizationStrategy::AtFrequency => (
+                measurement_normalizer::Normalization::AtFrequency { frequency_hz },
+                database::normalizations::Normalization {
+                    band_end_hz: None,
+                    band_start_hz: None,
+                    frequency_hz: Some(frequency_hz),
+                    strategy: "at-frequency".to_string(),
+                    target: None,
+                    target_hash: None,
+                },
+            ),
+            NormalizationStrategy::BandAverage => (
+                measurement_normalizer::Normalization::BandAverage {
+                    start_hz: band_start_hz,
+                    end_hz: band_end_hz,
+                },
+                database::normalizations::Normalization {
+                    band_end_hz: Some(band_end_hz),
+                    band_start_hz: Some(band_start_hz),
+                    frequency_hz: None,
+                    strategy: "band-average".to_string(),
+                    target: None,
+                    target_hash: None,
+                },
+            ),
+            NormalizationStrategy::Target => {
+                let target = self
+                    .normalization_target
+                    .as_deref()
+                    .context("the target strategy requires a target file")?;
+                let text = fs::read_to_string(target)?;
+                (
+                    measurement_normalizer::Normalization::Target {
+                        points: measurement_parser::parse(&text).with_context(|| {
+                            format!("the target file `{}` can't be parsed", target)
+                        })?,
+                        start_hz: band_start_hz,
+                        end_hz: band_end_hz,
+                    },
+                    database::normalizations::Normalization {
+                        band_end_hz: Some(band_end_hz),
+                        band_start_hz: Some(band_start_hz),
+                        frequency_hz: None,
+                        strategy: "target".to_string(),
+                        target: Some(target.to_string()),
+                        target_hash: Some(hasher::sha256_hex(text.as_bytes())),
+                    },
+                )
+            }
+        };
+        database::normalizations::delete(transaction)?;
+        database::normalizations::insert(transaction, &row)?;
+        database::transform_options::delete(transaction)?;
+        database::transform_options::insert(
+            transaction,
+            &database::transform_options::TransformOptions {
+                compact_points: self.compact_points,
+                resample_end_hz: self.resample_end_hz,
+                resample_points_per_octave: self.resample_points_per_octave,
+                resample_start_hz: self.resample_start_hz,
+                smoothing: self.smoothing,
+            },
+        )?;
+        Ok(normalization)
+    }
+}
+
+fn temporary_path(path: &str) -> Result<PathBuf, Error> {
+    let path = Path::new(path);
+    let file_name = path
+        .file_name()
+        .context("the output path has no file name")?
+        .to_string_lossy();
+    Ok(path.with_file_name(format!(".{}.{}.tmp", file_name, process::id())))
+}
+
+fn group_measurements(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let curves = database::resampled_points::select_curves(transaction)?;
+    database::measurement_groups::delete_all(transaction)?;
+    for (channel_id, group_id) in measurement_deduplicator::group(&curves) {
+        database::measurement_groups::insert(transaction, channel_id, group_id)?;
+    }
+    Ok(())
+}
+
+fn point_array(
+    transformed_channel: &TransformedChannel,
+) -> Vec<database::point_arrays::PointArrayRow> {
+    transformed_channel
+        .points
+        .iter()
+        .enumerate()
+        .map(|(index, point)| {
+            (
+                point.frequency_hz,
+                point.phase_degrees,
+                transformed_channel
+                    .smoothed_spl_db
+                    .as_ref()
+                    .map(|values| values[index]),
+                point.spl_db,
+            )
+        })
+        .collect()
+}
+
+fn pair_channels(transaction: &rusqlite::Transaction) -> Result<i64, Error> {
+    database::channel_pairs::delete_all(transaction)?;
+    database::channel_pairs::insert_from_channels(transaction)?;
+    let duplicate_count = database::channel_pairs::select_duplicate_count(transaction)?;
+    if duplicate_count > 0 {
+        println!(
+            "Left {} sample sides with several channels out of `channel_pairs`",
+            duplicate_count
+        );
+    }
+    Ok(duplicate_count)
+}
+
+fn delete_channel_rows(transaction: &rusqlite::Transaction, id: i32) -> Result<(), Error> {
+    database::channel_issues::delete(transaction, id)?;
+    database::points::delete(transaction, id)?;
+    database::resampled_points::delete(transaction, id)?;
+    database::point_arrays::delete(transaction, id)?;
+    database::channel_metrics::delete(transaction, id)?;
+    database::impedance_points::delete(transaction, id)?;
+    Ok(())
+}
+
+fn delete_derived_rows(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    database::measurement_groups::delete_all(transaction)?;
+    database::phone_average_points::delete_all(transaction)?;
+    database::file_average_points::delete_all(transaction)?;
+    database::sample_average_points::delete_all(transaction)?;
+    database::channel_pairs::delete_all(transaction)?;
+    database::phone_models::delete_all(transaction)?;
+    database::models::delete_all(transaction)?;
+    database::brand_canonical_brands::delete_all(transaction)?;
+    database::canonical_brands::delete_all(transaction)?;
+    database::phone_search::delete_all(transaction)?;
+    Ok(())
+}
+
+fn index_phones(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    database::phone_search::delete_all(transaction)?;
+    database::phone_search::insert_from_phones(transaction)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use clap::Parser;
+    use std::env;
+
+    use super::*;
+
+    fn execute(arguments: &[&str]) -> Result<(), Error> {
+        Command::try_parse_from([&["transform"], arguments].concat())?.execute()
+    }
+
+    fn select_ids(path: &Path, table_name: &str) -> Result<Vec<i32>, Error> {
+        let connection = rusqlite::Connection::open(path)?;
+        let mut statement =
+            connection.prepare(&format!("SELECT id FROM {table_name} ORDER BY id"))?;
+        let ids = statement
+            .query_map([], |row| row.get(0))?
+            .collect::<Result<Vec<i32>, rusqlite::Error>>()?;
+        Ok(ids)
+    }
+
+    #[test]
+    fn it_removes_deleted_channels_and_files_incrementally() -> Result<(), Error> {
+        let directory = env::temp_dir();
+        let input = directory.join(format!("wobbling-statistics-{}-input", process::id()));
+        let output = directory.join(format!("wobbling-statistics-{}-output", process::id()));
+        let _ = fs::remove_file(&output);
+        let mut connection = rusqlite::Connection::open(&input)?;
+        let transaction = connection.transaction()?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Raw)?;
+        transaction.execute_batch(indoc::indoc!(
+            "
+            DELETE FROM sites;
+            INSERT INTO sites (id, name, username) VALUES (1, 'Site', 'site');
+            INSERT INTO dbs (id, folder, site_id, type) VALUES (1, '/', 1, 'IEMs');
+            INSERT INTO brands (id, name, site_id) VALUES (1, 'Moondrop', 1);
+            INSERT INTO phones (id, brand_id, name) VALUES (1, 1, 'Aria'), (2, 1, 'Kato');
+            INSERT INTO files (id, phone_id, text) VALUES (1, 1, 'Aria'), (2, 2, 'Kato');
+            "
+        ))?;
+        for (id, file_id, type_) in [(1, 1, "Left"), (2, 1, "Right"), (3, 2, "Left")] {
+            let text = format!("20 {id}\n1000 90\n20000 80\n");
+            transaction.execute(
+                "INSERT INTO channels (id, content_hash, file_id, idx, text, type) \
+                 VALUES (?, ?, ?, 0, ?, ?)",
+                (
+                    id,
+                    hasher::sha256_hex(text.as_bytes()),
+                    file_id,
+                    &text,
+                    type_,
+                ),
+            )?;
+        }
         transaction.commit()?;
+        let arguments = [
+            "-i",
+            input.to_str().unwrap(),
+            "-o",
+            output.to_str().unwrap(),
+            "--incremental",
+        ];
+        execute(&arguments)?;
+
+        connection.execute("DELETE FROM channels WHERE id = 2", [])?;
+        execute(&arguments)?;
+        let channel_ids = select_ids(&output, "channels")?;
+
+        connection
+            .execute_batch("DELETE FROM channels WHERE id = 3; DELETE FROM files WHERE id = 2")?;
+        execute(&arguments)?;
+        let file_ids = select_ids(&output, "files")?;
+        let last_channel_ids = select_ids(&output, "channels")?;
 
+        fs::remove_file(&input)?;
+        fs::remove_file(&output)?;
+        assert_eq!(channel_ids, vec![1, 3]);
+        assert_eq!(file_ids, vec![1]);
+        assert_eq!(last_channel_ids, vec![1]);
         Ok(())
     }
 }
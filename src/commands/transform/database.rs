@@ -1,2 +1,25 @@
+pub mod blobs;
+pub mod brand_canonical_brands;
+pub mod brands;
+pub mod canonical_brands;
+pub mod channel_issues;
+pub mod channel_metrics;
+pub mod channel_pairs;
 pub mod channels;
+pub mod file_average_points;
+pub mod impedance_points;
+pub mod input;
+pub mod measurement_groups;
+pub mod migrations;
+pub mod models;
+pub mod normalizations;
+pub mod phone_average_points;
+pub mod phone_models;
+pub mod phone_search;
+pub mod phones;
+pub mod point_arrays;
 pub mod points;
+pub mod resampled_points;
+pub mod sample_average_points;
+pub mod transform_options;
+pub mod views;
@@ -0,0 +1,7 @@
+use anyhow::Error;
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM blobs";
+    transaction.execute(query, [])?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS brand_canonical_brands (
+            brand_id           INTEGER PRIMARY KEY REFERENCES brands(id),
+            canonical_brand_id INTEGER REFERENCES canonical_brands(id)
+        );
+        CREATE INDEX IF NOT EXISTS brand_canonical_brands_canonical_brand_id_idx
+        ON brand_canonical_brands(canonical_brand_id);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM brand_canonical_brands";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    brand_id: i64,
+    canonical_brand_id: i64,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO brand_canonical_brands (brand_id, canonical_brand_id)
+        VALUES (?, ?)
+        "
+    )
+    .trim_end();
+    let params = (brand_id, canonical_brand_id);
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
@@ -0,0 +1,17 @@
+use anyhow::Error;
+
+use crate::identity_resolver::Brand;
+
+pub fn select(transaction: &rusqlite::Transaction) -> Result<Vec<Brand>, Error> {
+    let query = "SELECT id, name FROM brands";
+    let mut statement = transaction.prepare(query)?;
+    let brands = statement
+        .query_map([], |row| {
+            Ok(Brand {
+                id: row.get(0)?,
+                name: row.get(1)?,
+            })
+        })?
+        .collect::<Result<Vec<Brand>, rusqlite::Error>>()?;
+    Ok(brands)
+}
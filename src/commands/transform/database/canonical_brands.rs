@@ -0,0 +1,37 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS canonical_brands (
+            id   INTEGER PRIMARY KEY,
+            name TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS canonical_brands_name_idx
+        ON canonical_brands(name);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM canonical_brands";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert(transaction: &rusqlite::Transaction, id: i64, name: &str) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO canonical_brands (id, name)
+        VALUES (?, ?)
+        "
+    )
+    .trim_end();
+    let params = (id, name);
+    transaction.execute(query, params)?;
+    Ok(())
+}
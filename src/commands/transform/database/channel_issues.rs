@@ -0,0 +1,45 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS channel_issues (
+            id         INTEGER PRIMARY KEY,
+            channel_id INTEGER REFERENCES channels(id),
+            issue      TEXT NOT NULL,
+            severity   TEXT NOT NULL,
+            UNIQUE(channel_id, issue)
+        );
+        CREATE INDEX IF NOT EXISTS channel_issues_channel_id_idx
+        ON channel_issues(channel_id);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete(transaction: &rusqlite::Transaction, channel_id: i32) -> Result<(), Error> {
+    let query = "DELETE FROM channel_issues WHERE channel_id = ?";
+    transaction.execute(query, [channel_id])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    channel_id: i32,
+    issue: &str,
+    severity: &str,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT OR IGNORE INTO channel_issues (channel_id, issue, severity)
+        VALUES (?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (channel_id, issue, severity);
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
@@ -0,0 +1,95 @@
+use anyhow::Error;
+use indoc::indoc;
+
+use crate::measurement_summarizer::Metrics;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS channel_metrics (
+            channel_id               INTEGER PRIMARY KEY REFERENCES channels(id),
+            air_db                   REAL,
+            bass_db                  REAL,
+            ear_gain_db              REAL,
+            ear_gain_frequency_hz    REAL,
+            low_mid_db               REAL,
+            max_frequency_hz         REAL,
+            mid_db                   REAL,
+            min_frequency_hz         REAL,
+            presence_db              REAL,
+            sub_bass_db              REAL,
+            tilt_db_per_octave       REAL,
+            treble_db                REAL,
+            treble_dip_db            REAL,
+            treble_dip_frequency_hz  REAL,
+            treble_peak_db           REAL,
+            treble_peak_frequency_hz REAL,
+            upper_mid_db             REAL
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete(transaction: &rusqlite::Transaction, channel_id: i32) -> Result<(), Error> {
+    let query = "DELETE FROM channel_metrics WHERE channel_id = ?";
+    transaction.execute(query, [channel_id])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    channel_id: i32,
+    metrics: &Metrics,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT OR REPLACE INTO channel_metrics (
+            channel_id,
+            air_db,
+            bass_db,
+            ear_gain_db,
+            ear_gain_frequency_hz,
+            low_mid_db,
+            max_frequency_hz,
+            mid_db,
+            min_frequency_hz,
+            presence_db,
+            sub_bass_db,
+            tilt_db_per_octave,
+            treble_db,
+            treble_dip_db,
+            treble_dip_frequency_hz,
+            treble_peak_db,
+            treble_peak_frequency_hz,
+            upper_mid_db
+        )
+        VALUES (?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = rusqlite::params![
+        channel_id,
+        metrics.air_db,
+        metrics.bass_db,
+        metrics.ear_gain_db,
+        metrics.ear_gain_frequency_hz,
+        metrics.low_mid_db,
+        metrics.max_frequency_hz,
+        metrics.mid_db,
+        metrics.min_frequency_hz,
+        metrics.presence_db,
+        metrics.sub_bass_db,
+        metrics.tilt_db_per_octave,
+        metrics.treble_db,
+        metrics.treble_dip_db,
+        metrics.treble_dip_frequency_hz,
+        metrics.treble_peak_db,
+        metrics.treble_peak_frequency_hz,
+        metrics.upper_mid_db,
+    ];
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
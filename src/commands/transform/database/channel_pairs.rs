@@ -0,0 +1,185 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS channel_pairs (
+            id               INTEGER PRIMARY KEY,
+            file_id          INTEGER NOT NULL REFERENCES files(id),
+            idx              INTEGER NOT NULL,
+            left_channel_id  INTEGER REFERENCES channels(id),
+            mono_channel_id  INTEGER REFERENCES channels(id),
+            right_channel_id INTEGER REFERENCES channels(id),
+            UNIQUE(file_id, idx)
+        );
+        CREATE INDEX IF NOT EXISTS channel_pairs_left_channel_id_idx
+        ON channel_pairs(left_channel_id);
+        CREATE INDEX IF NOT EXISTS channel_pairs_mono_channel_id_idx
+        ON channel_pairs(mono_channel_id);
+        CREATE INDEX IF NOT EXISTS channel_pairs_right_channel_id_idx
+        ON channel_pairs(right_channel_id);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM channel_pairs";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert_from_channels(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO channel_pairs (
+            file_id,
+            idx,
+            left_channel_id,
+            mono_channel_id,
+            right_channel_id
+        )
+        SELECT
+            file_id,
+            idx,
+            CASE
+                WHEN COUNT(CASE WHEN type = 'Left' THEN 1 END) = 1
+                THEN MIN(CASE WHEN type = 'Left' THEN id END)
+            END,
+            CASE
+                WHEN COUNT(CASE WHEN type IS NULL THEN 1 END) = 1
+                THEN MIN(CASE WHEN type IS NULL THEN id END)
+            END,
+            CASE
+                WHEN COUNT(CASE WHEN type = 'Right' THEN 1 END) = 1
+                THEN MIN(CASE WHEN type = 'Right' THEN id END)
+            END
+        FROM channels
+        GROUP BY file_id, idx
+        "
+    )
+    .trim_end();
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn select_duplicate_count(transaction: &rusqlite::Transaction) -> Result<i64, Error> {
+    let query = indoc!(
+        "
+        SELECT COUNT(*)
+        FROM (
+            SELECT 1
+            FROM channels
+            GROUP BY file_id, idx, type
+            HAVING COUNT(*) > 1
+        )
+        "
+    )
+    .trim_end();
+    let result = transaction.query_row(query, [], |row| row.get(0))?;
+    Ok(result)
+}
+
+#[derive(Debug)]
+pub struct Sample {
+    pub phone_id: i64,
+    pub file_id: i64,
+    pub idx: i64,
+    pub left_channel_id: Option<i32>,
+    pub mono_channel_id: Option<i32>,
+    pub right_channel_id: Option<i32>,
+}
+
+pub fn select_samples(transaction: &rusqlite::Transaction) -> Result<Vec<Sample>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            files.phone_id,
+            channel_pairs.file_id,
+            channel_pairs.idx,
+            left_channels.id,
+            mono_channels.id,
+            right_channels.id
+        FROM channel_pairs
+        JOIN files ON files.id = channel_pairs.file_id
+        LEFT JOIN channels left_channels ON left_channels.id = channel_pairs.left_channel_id
+        AND left_channels.quality != 'error'
+        LEFT JOIN channels mono_channels ON mono_channels.id = channel_pairs.mono_channel_id
+        AND mono_channels.quality != 'error'
+        LEFT JOIN channels right_channels ON right_channels.id = channel_pairs.right_channel_id
+        AND right_channels.quality != 'error'
+        ORDER BY files.phone_id, channel_pairs.file_id, channel_pairs.idx
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let samples = statement
+        .query_map([], |row| {
+            Ok(Sample {
+                phone_id: row.get(0)?,
+                file_id: row.get(1)?,
+                idx: row.get(2)?,
+                left_channel_id: row.get(3)?,
+                mono_channel_id: row.get(4)?,
+                right_channel_id: row.get(5)?,
+            })
+        })?
+        .collect::<Result<Vec<Sample>, rusqlite::Error>>()?;
+    Ok(samples)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::schema_migrator;
+
+    #[test]
+    fn it_pairs_sides_with_one_channel_each() -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let transaction = connection.transaction()?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Raw)?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Transformed)?;
+        transaction.execute_batch(indoc!(
+            "
+            INSERT INTO files (id, text) VALUES (1, 'Aria');
+            INSERT INTO channels (id, content_hash, file_id, idx, type)
+            VALUES
+                (1, '1', 1, 0, 'Left'),
+                (2, '2', 1, 0, 'Right'),
+                (3, '3', 1, 1, 'Left'),
+                (4, '4', 1, 1, 'Left'),
+                (5, '5', 1, 1, 'Right'),
+                (6, '6', 1, 2, NULL);
+            "
+        ))?;
+
+        insert_from_channels(&transaction)?;
+        let mut statement = transaction.prepare(indoc!(
+            "
+            SELECT idx, left_channel_id, mono_channel_id, right_channel_id
+            FROM channel_pairs
+            ORDER BY idx
+            "
+        ))?;
+        let pairs = statement
+            .query_map([], |row| {
+                Ok((row.get(0)?, row.get(1)?, row.get(2)?, row.get(3)?))
+            })?
+            .collect::<Result<Vec<(i64, Option<i64>, Option<i64>, Option<i64>)>, rusqlite::Error>>(
+            )?;
+
+        assert_eq!(
+            pairs,
+            vec![
+                (0, Some(1), None, Some(2)),
+                (1, None, None, Some(5)),
+                (2, None, Some(6), None)
+            ]
+        );
+        assert_eq!(select_duplicate_count(&transaction)?, 1);
+        Ok(())
+    }
+}
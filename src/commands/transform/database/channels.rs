@@ -1,12 +1,48 @@
 use anyhow::Error;
 use indoc::indoc;
 
+use crate::text_compressor;
+
+#[derive(Debug)]
+pub struct Channel {
+    pub id: i32,
+    pub content_hash: String,
+    pub file_id: i32,
+    pub idx: i32,
+    pub text: String,
+    pub type_: Option<String>,
+}
+
 #[derive(Debug)]
 pub struct IdAndText {
     pub id: i32,
     pub text: String,
 }
 
+pub fn add_column_quality(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "ALTER TABLE channels ADD COLUMN quality TEXT";
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn add_column_text_hash(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "ALTER TABLE channels ADD COLUMN text_hash TEXT";
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn clear_text_hash(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "UPDATE channels SET text_hash = NULL";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn delete(transaction: &rusqlite::Transaction, id: i32) -> Result<(), Error> {
+    let query = "DELETE FROM channels WHERE id = ?";
+    transaction.execute(query, [id])?;
+    Ok(())
+}
+
 pub fn drop_column_text(transaction: &rusqlite::Transaction) -> Result<(), Error> {
     let query = indoc!(
         "
@@ -14,17 +50,18 @@ pub fn drop_column_text(transaction: &rusqlite::Transaction) -> Result<(), Error
         RENAME TO old_channels;
 
         CREATE TABLE IF NOT EXISTS channels (
-            id      INTEGER PRIMARY KEY,
-            file_id INTEGER REFERENCES files(id),
-            idx     INTEGER NOT NULL,
-            type    TEXT
+            id           INTEGER PRIMARY KEY,
+            content_hash TEXT NOT NULL,
+            file_id      INTEGER REFERENCES files(id),
+            idx          INTEGER NOT NULL,
+            type         TEXT
         );
 
         CREATE INDEX IF NOT EXISTS channels_file_id_idx
         ON channels(file_id);
 
         INSERT INTO channels
-        SELECT id, file_id, idx, type
+        SELECT id, content_hash, file_id, idx, type
         FROM old_channels;
 
         DROP TABLE old_channels;
@@ -35,15 +72,144 @@ pub fn drop_column_text(transaction: &rusqlite::Transaction) -> Result<(), Error
     Ok(())
 }
 
+pub fn has_column_text(transaction: &rusqlite::Transaction) -> Result<bool, Error> {
+    let query = "SELECT EXISTS(SELECT 1 FROM pragma_table_info('channels') WHERE name = 'text')";
+    let result = transaction.query_row(query, [], |row| row.get(0))?;
+    Ok(result)
+}
+
 pub fn select(transaction: &rusqlite::Transaction) -> Result<Vec<IdAndText>, Error> {
-    let query = "SELECT id, text FROM channels";
+    let query = indoc!(
+        "
+        SELECT channels.id, channels.text, blobs.data
+        FROM channels
+        LEFT JOIN blobs ON blobs.id = channels.blob_id
+        "
+    )
+    .trim_end();
     let mut statement = transaction.prepare(query)?;
-    let channel_iter = statement.query_map([], |row| {
-        Ok(IdAndText {
+    let mut rows = statement.query([])?;
+    let mut id_and_texts = vec![];
+    while let Some(row) = rows.next()? {
+        id_and_texts.push(IdAndText {
             id: row.get(0)?,
-            text: row.get(1)?,
-        })
-    })?;
-    let id_and_texts = channel_iter.collect::<Result<Vec<IdAndText>, rusqlite::Error>>()?;
+            text: text_compressor::text(row.get(1)?, row.get(2)?)?,
+        });
+    }
     Ok(id_and_texts)
 }
+
+pub fn select_removed_ids(transaction: &rusqlite::Transaction) -> Result<Vec<i32>, Error> {
+    let query = "SELECT id FROM channels WHERE id NOT IN (SELECT id FROM input.channels)";
+    let mut statement = transaction.prepare(query)?;
+    let ids = statement
+        .query_map([], |row| row.get(0))?
+        .collect::<Result<Vec<i32>, rusqlite::Error>>()?;
+    Ok(ids)
+}
+
+pub fn select_changed_input(transaction: &rusqlite::Transaction) -> Result<Vec<Channel>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            input_channels.id,
+            input_channels.content_hash,
+            input_channels.file_id,
+            input_channels.idx,
+            input_channels.text,
+            input_blobs.data,
+            input_channels.type
+        FROM input.channels input_channels
+        LEFT JOIN input.blobs input_blobs ON input_blobs.id = input_channels.blob_id
+        LEFT JOIN channels ON channels.id = input_channels.id
+        WHERE channels.text_hash IS NOT input_channels.content_hash
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let mut rows = statement.query([])?;
+    let mut channels = vec![];
+    while let Some(row) = rows.next()? {
+        channels.push(Channel {
+            id: row.get(0)?,
+            content_hash: row.get(1)?,
+            file_id: row.get(2)?,
+            idx: row.get(3)?,
+            text: text_compressor::text(row.get(4)?, row.get(5)?)?,
+            type_: row.get(6)?,
+        });
+    }
+    Ok(channels)
+}
+
+pub fn update_text(transaction: &rusqlite::Transaction, id: i32, text: &str) -> Result<(), Error> {
+    let query = "UPDATE channels SET blob_id = NULL, text = ? WHERE id = ? AND text IS NULL";
+    let params = (text, id);
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
+
+pub fn update_quality(
+    transaction: &rusqlite::Transaction,
+    id: i32,
+    quality: &str,
+) -> Result<(), Error> {
+    let query = "UPDATE channels SET quality = ? WHERE id = ?";
+    let params = (quality, id);
+    transaction.execute(query, params)?;
+    Ok(())
+}
+
+pub fn update_text_hash(transaction: &rusqlite::Transaction, id: i32) -> Result<(), Error> {
+    let query = "UPDATE channels SET text_hash = content_hash WHERE id = ?";
+    transaction.execute(query, [id])?;
+    Ok(())
+}
+
+pub fn update_text_hashes(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "UPDATE channels SET text_hash = content_hash";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn upsert(transaction: &rusqlite::Transaction, channel: &Channel) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT OR REPLACE INTO channels (id, content_hash, file_id, idx, type)
+        VALUES (?, ?, ?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (
+        channel.id,
+        &channel.content_hash,
+        channel.file_id,
+        channel.idx,
+        &channel.type_,
+    );
+    transaction.execute(query, params)?;
+    Ok(())
+}
+
+pub fn upsert_with_text(
+    transaction: &rusqlite::Transaction,
+    channel: &Channel,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT OR REPLACE INTO channels (id, content_hash, file_id, idx, text, type)
+        VALUES (?, ?, ?, ?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (
+        channel.id,
+        &channel.content_hash,
+        channel.file_id,
+        channel.idx,
+        &channel.text,
+        &channel.type_,
+    );
+    transaction.execute(query, params)?;
+    Ok(())
+}
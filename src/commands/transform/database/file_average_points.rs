@@ -0,0 +1,63 @@
+use anyhow::Error;
+use indoc::indoc;
+
+use crate::measurement_averager::AveragedPoint;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS file_average_points (
+            id                INTEGER PRIMARY KEY,
+            file_id           INTEGER NOT NULL REFERENCES files(id),
+            channel_count     INTEGER NOT NULL,
+            frequency_hz      REAL NOT NULL,
+            idx               INTEGER NOT NULL,
+            normalized_spl_db REAL,
+            spl_db            REAL NOT NULL,
+            UNIQUE(file_id, idx)
+        );
+        CREATE INDEX IF NOT EXISTS file_average_points_file_id_idx
+        ON file_average_points(file_id);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM file_average_points";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    file_id: i64,
+    point: &AveragedPoint,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO file_average_points (
+            file_id,
+            channel_count,
+            frequency_hz,
+            idx,
+            normalized_spl_db,
+            spl_db
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (
+        file_id,
+        point.channel_count,
+        point.frequency_hz,
+        point.idx,
+        point.normalized_spl_db,
+        point.spl_db,
+    );
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
@@ -0,0 +1,61 @@
+use anyhow::Error;
+use indoc::indoc;
+
+use crate::measurement_parser::ImpedancePoint;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS impedance_points (
+            id             INTEGER PRIMARY KEY,
+            channel_id     INTEGER REFERENCES channels(id),
+            frequency_hz   REAL NOT NULL,
+            idx            INTEGER NOT NULL,
+            impedance_ohms REAL NOT NULL,
+            phase_degrees  REAL,
+            UNIQUE(channel_id, idx)
+        );
+        CREATE INDEX IF NOT EXISTS impedance_points_channel_id_idx
+        ON impedance_points(channel_id);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete(transaction: &rusqlite::Transaction, channel_id: i32) -> Result<(), Error> {
+    let query = "DELETE FROM impedance_points WHERE channel_id = ?";
+    transaction.execute(query, [channel_id])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    channel_id: i32,
+    idx: i32,
+    point: &ImpedancePoint,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT OR IGNORE INTO impedance_points (
+            channel_id,
+            frequency_hz,
+            idx,
+            impedance_ohms,
+            phase_degrees
+        )
+        VALUES (?, ?, ?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (
+        channel_id,
+        point.frequency_hz,
+        idx,
+        point.impedance_ohms,
+        point.phase_degrees,
+    );
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
@@ -0,0 +1,31 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn attach(connection: &rusqlite::Connection, path: &str) -> Result<(), Error> {
+    let query = "ATTACH DATABASE ? AS input";
+    connection.execute(query, [path])?;
+    Ok(())
+}
+
+pub fn sync(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        DELETE FROM suffixes WHERE id NOT IN (SELECT id FROM input.suffixes);
+        DELETE FROM files WHERE id NOT IN (SELECT id FROM input.files);
+        DELETE FROM phones WHERE id NOT IN (SELECT id FROM input.phones);
+        DELETE FROM brands WHERE id NOT IN (SELECT id FROM input.brands);
+        DELETE FROM dbs WHERE id NOT IN (SELECT id FROM input.dbs);
+        DELETE FROM sites WHERE id NOT IN (SELECT id FROM input.sites);
+
+        INSERT OR REPLACE INTO sites SELECT * FROM input.sites;
+        INSERT OR REPLACE INTO dbs SELECT * FROM input.dbs;
+        INSERT OR REPLACE INTO brands SELECT * FROM input.brands;
+        INSERT OR REPLACE INTO phones SELECT * FROM input.phones;
+        INSERT OR REPLACE INTO files SELECT * FROM input.files;
+        INSERT OR REPLACE INTO suffixes SELECT * FROM input.suffixes;
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
@@ -0,0 +1,41 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS measurement_groups (
+            channel_id INTEGER PRIMARY KEY REFERENCES channels(id),
+            group_id   INTEGER NOT NULL REFERENCES channels(id)
+        );
+        CREATE INDEX IF NOT EXISTS measurement_groups_group_id_idx
+        ON measurement_groups(group_id);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM measurement_groups";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    channel_id: i32,
+    group_id: i32,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO measurement_groups (channel_id, group_id)
+        VALUES (?, ?)
+        "
+    )
+    .trim_end();
+    let params = (channel_id, group_id);
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
@@ -0,0 +1,67 @@
+use anyhow::Error;
+
+use super::{
+    brand_canonical_brands, canonical_brands, channel_issues, channel_metrics, channel_pairs,
+    channels, file_average_points, impedance_points, measurement_groups, models, normalizations,
+    phone_average_points, phone_models, phone_search, point_arrays, points, resampled_points,
+    sample_average_points, transform_options, views,
+};
+use crate::commands::schema_migrator::Migration;
+
+pub const MIGRATIONS: &[Migration] = &[
+    create_tables,
+    create_channel_pairs,
+    create_average_points,
+    create_views,
+    create_phone_search,
+    create_channel_metrics,
+    create_point_arrays,
+];
+
+fn create_tables(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    channels::add_column_quality(transaction)?;
+    channels::add_column_text_hash(transaction)?;
+    points::create(transaction)?;
+    impedance_points::create(transaction)?;
+    resampled_points::create(transaction)?;
+    normalizations::create(transaction)?;
+    transform_options::create(transaction)?;
+    canonical_brands::create(transaction)?;
+    brand_canonical_brands::create(transaction)?;
+    models::create(transaction)?;
+    phone_models::create(transaction)?;
+    measurement_groups::create(transaction)?;
+    channel_issues::create(transaction)?;
+    Ok(())
+}
+
+fn create_channel_pairs(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    channel_pairs::create(transaction)
+}
+
+fn create_average_points(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    sample_average_points::create(transaction)?;
+    file_average_points::create(transaction)?;
+    phone_average_points::create(transaction)?;
+    Ok(())
+}
+
+fn create_views(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    views::create(transaction)
+}
+
+fn create_phone_search(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    phone_search::create(transaction)
+}
+
+fn create_channel_metrics(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    channel_metrics::create(transaction)?;
+    channels::clear_text_hash(transaction)?;
+    Ok(())
+}
+
+fn create_point_arrays(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    point_arrays::create(transaction)?;
+    views::create_points(transaction)?;
+    Ok(())
+}
@@ -0,0 +1,45 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS models (
+            id                 INTEGER PRIMARY KEY,
+            canonical_brand_id INTEGER REFERENCES canonical_brands(id),
+            name               TEXT NOT NULL
+        );
+        CREATE INDEX IF NOT EXISTS models_canonical_brand_id_idx
+        ON models(canonical_brand_id);
+        CREATE INDEX IF NOT EXISTS models_name_idx
+        ON models(name);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM models";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    id: i64,
+    canonical_brand_id: i64,
+    name: &str,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO models (id, canonical_brand_id, name)
+        VALUES (?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (id, canonical_brand_id, name);
+    transaction.execute(query, params)?;
+    Ok(())
+}
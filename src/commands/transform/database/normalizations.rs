@@ -0,0 +1,91 @@
+use anyhow::Error;
+use indoc::indoc;
+use rusqlite::OptionalExtension;
+
+#[derive(Debug, PartialEq)]
+pub struct Normalization {
+    pub band_end_hz: Option<f64>,
+    pub band_start_hz: Option<f64>,
+    pub frequency_hz: Option<f64>,
+    pub strategy: String,
+    pub target: Option<String>,
+    pub target_hash: Option<String>,
+}
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS normalizations (
+            id            INTEGER PRIMARY KEY,
+            band_end_hz   REAL,
+            band_start_hz REAL,
+            frequency_hz  REAL,
+            strategy      TEXT NOT NULL,
+            target        TEXT,
+            target_hash   TEXT
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM normalizations";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    normalization: &Normalization,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO normalizations (
+            band_end_hz,
+            band_start_hz,
+            frequency_hz,
+            strategy,
+            target,
+            target_hash
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (
+        normalization.band_end_hz,
+        normalization.band_start_hz,
+        normalization.frequency_hz,
+        &normalization.strategy,
+        &normalization.target,
+        &normalization.target_hash,
+    );
+    transaction.execute(query, params)?;
+    Ok(())
+}
+
+pub fn select(transaction: &rusqlite::Transaction) -> Result<Option<Normalization>, Error> {
+    let query = indoc!(
+        "
+        SELECT band_end_hz, band_start_hz, frequency_hz, strategy, target, target_hash
+        FROM normalizations
+        "
+    )
+    .trim_end();
+    let result = transaction
+        .query_row(query, [], |row| {
+            Ok(Normalization {
+                band_end_hz: row.get(0)?,
+                band_start_hz: row.get(1)?,
+                frequency_hz: row.get(2)?,
+                strategy: row.get(3)?,
+                target: row.get(4)?,
+                target_hash: row.get(5)?,
+            })
+        })
+        .optional()?;
+    Ok(result)
+}
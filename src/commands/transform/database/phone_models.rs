@@ -0,0 +1,41 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS phone_models (
+            phone_id INTEGER PRIMARY KEY REFERENCES phones(id),
+            model_id INTEGER REFERENCES models(id)
+        );
+        CREATE INDEX IF NOT EXISTS phone_models_model_id_idx
+        ON phone_models(model_id);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM phone_models";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    phone_id: i64,
+    model_id: i64,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO phone_models (phone_id, model_id)
+        VALUES (?, ?)
+        "
+    )
+    .trim_end();
+    let params = (phone_id, model_id);
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
@@ -0,0 +1,64 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE VIRTUAL TABLE IF NOT EXISTS phone_search USING fts5(
+            phone_id UNINDEXED,
+            brand,
+            canonical_brand,
+            phone,
+            model,
+            suffixes,
+            files,
+            tokenize = 'unicode61 remove_diacritics 2'
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete_all(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM phone_search";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert_from_phones(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO phone_search (
+            phone_id,
+            brand,
+            canonical_brand,
+            phone,
+            model,
+            suffixes,
+            files
+        )
+        SELECT
+            phone_id,
+            brand,
+            canonical_brand,
+            phone,
+            model,
+            (
+                SELECT GROUP_CONCAT(text, ' ')
+                FROM suffixes
+                WHERE suffixes.phone_id = v_phones.phone_id
+            ),
+            (
+                SELECT GROUP_CONCAT(text, ' ')
+                FROM files
+                WHERE files.phone_id = v_phones.phone_id
+            )
+        FROM v_phones
+        "
+    )
+    .trim_end();
+    transaction.execute(query, [])?;
+    Ok(())
+}
@@ -0,0 +1,18 @@
+use anyhow::Error;
+
+use crate::identity_resolver::Phone;
+
+pub fn select(transaction: &rusqlite::Transaction) -> Result<Vec<Phone>, Error> {
+    let query = "SELECT id, brand_id, name FROM phones";
+    let mut statement = transaction.prepare(query)?;
+    let phones = statement
+        .query_map([], |row| {
+            Ok(Phone {
+                id: row.get(0)?,
+                brand_id: row.get(1)?,
+                name: row.get(2)?,
+            })
+        })?
+        .collect::<Result<Vec<Phone>, rusqlite::Error>>()?;
+    Ok(phones)
+}
@@ -0,0 +1,92 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub type PointArrayRow = (f64, Option<f64>, Option<f64>, f64);
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS point_arrays (
+            channel_id INTEGER PRIMARY KEY REFERENCES channels(id),
+            points     TEXT NOT NULL
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete(transaction: &rusqlite::Transaction, channel_id: i32) -> Result<(), Error> {
+    let query = "DELETE FROM point_arrays WHERE channel_id = ?";
+    transaction.execute(query, [channel_id])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    channel_id: i32,
+    points: &[PointArrayRow],
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT OR REPLACE INTO point_arrays (channel_id, points)
+        VALUES (?, ?)
+        "
+    )
+    .trim_end();
+    let params = (channel_id, serde_json::to_string(points)?);
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::commands::schema_migrator;
+
+    #[test]
+    fn it_reads_point_arrays_through_v_points() -> Result<(), Error> {
+        let mut connection = rusqlite::Connection::open_in_memory()?;
+        let transaction = connection.transaction()?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Raw)?;
+        schema_migrator::migrate(&transaction, schema_migrator::Schema::Transformed)?;
+        transaction.execute(
+            "INSERT INTO channels (id, content_hash, idx) VALUES (1, '', 0)",
+            [],
+        )?;
+        let points = [
+            (20.0, None, Some(80.0), 80.0),
+            (1000.0, Some(-10.0), Some(85.0), f64::NAN),
+            (20000.0, None, Some(90.0), 90.0),
+        ];
+
+        insert(&transaction, 1, &points)?;
+        let rows = transaction
+            .prepare(
+                "SELECT channel_id, frequency_hz, idx, phase_degrees, smoothed_spl_db, spl_db \
+                 FROM v_points ORDER BY idx",
+            )?
+            .query_map([], |row| {
+                Ok((
+                    row.get::<_, i32>(0)?,
+                    row.get::<_, f64>(1)?,
+                    row.get::<_, i32>(2)?,
+                    row.get::<_, Option<f64>>(3)?,
+                    row.get::<_, Option<f64>>(4)?,
+                    row.get::<_, Option<f64>>(5)?,
+                ))
+            })?
+            .collect::<Result<Vec<_>, rusqlite::Error>>()?;
+
+        assert_eq!(
+            rows,
+            vec![
+                (1, 20.0, 0, None, Some(80.0), Some(80.0)),
+                (1, 1000.0, 1, Some(-10.0), Some(85.0), None),
+                (1, 20000.0, 2, None, Some(90.0), Some(90.0)),
+            ]
+        );
+        Ok(())
+    }
+}
@@ -5,12 +5,13 @@ pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
     let query = indoc!(
         "
         CREATE TABLE IF NOT EXISTS points (
-            id            INTEGER PRIMARY KEY,
-            channel_id    INTEGER REFERENCES channels(id),
-            frequency_hz  REAL NOT NULL,
-            idx           INTEGER NOT NULL,
-            phase_degrees REAL,
-            spl_db        REAL NOT NULL,
+            id              INTEGER PRIMARY KEY,
+            channel_id      INTEGER REFERENCES channels(id),
+            frequency_hz    REAL NOT NULL,
+            idx             INTEGER NOT NULL,
+            phase_degrees   REAL,
+            smoothed_spl_db REAL,
+            spl_db          REAL NOT NULL,
             UNIQUE(channel_id, idx)
         );
         CREATE INDEX IF NOT EXISTS points_channel_id_idx
@@ -22,22 +23,43 @@ pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
     Ok(())
 }
 
+pub fn delete(transaction: &rusqlite::Transaction, channel_id: i32) -> Result<(), Error> {
+    let query = "DELETE FROM points WHERE channel_id = ?";
+    transaction.execute(query, [channel_id])?;
+    Ok(())
+}
+
 pub fn insert(
     transaction: &rusqlite::Transaction,
     channel_id: i32,
     frequency_hz: f64,
     idx: i32,
     phase_degrees: Option<f64>,
+    smoothed_spl_db: Option<f64>,
     spl_db: f64,
 ) -> Result<(), Error> {
     let query = indoc!(
         "
-        INSERT OR IGNORE INTO points (channel_id, frequency_hz, idx, phase_degrees, spl_db)
-        VALUES (?, ?, ?, ?, ?)
+        INSERT OR IGNORE INTO points (
+            channel_id,
+            frequency_hz,
+            idx,
+            phase_degrees,
+            smoothed_spl_db,
+            spl_db
+        )
+        VALUES (?, ?, ?, ?, ?, ?)
         "
     )
     .trim_end();
-    let params = (channel_id, frequency_hz, idx, phase_degrees, spl_db);
-    transaction.execute(query, params)?;
+    let params = (
+        channel_id,
+        frequency_hz,
+        idx,
+        phase_degrees,
+        smoothed_spl_db,
+        spl_db,
+    );
+    transaction.prepare_cached(query)?.execute(params)?;
     Ok(())
 }
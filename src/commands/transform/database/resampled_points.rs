@@ -0,0 +1,118 @@
+use anyhow::Error;
+use indoc::indoc;
+
+use crate::measurement_averager::AveragedPoint;
+use crate::measurement_deduplicator::Curve;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS resampled_points (
+            id                INTEGER PRIMARY KEY,
+            channel_id        INTEGER REFERENCES channels(id),
+            frequency_hz      REAL NOT NULL,
+            idx               INTEGER NOT NULL,
+            normalized_spl_db REAL,
+            spl_db            REAL NOT NULL,
+            UNIQUE(channel_id, idx)
+        );
+        CREATE INDEX IF NOT EXISTS resampled_points_channel_id_idx
+        ON resampled_points(channel_id);
+        CREATE INDEX IF NOT EXISTS resampled_points_idx_idx
+        ON resampled_points(idx);
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete(transaction: &rusqlite::Transaction, channel_id: i32) -> Result<(), Error> {
+    let query = "DELETE FROM resampled_points WHERE channel_id = ?";
+    transaction.execute(query, [channel_id])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    channel_id: i32,
+    frequency_hz: f64,
+    idx: i32,
+    normalized_spl_db: Option<f64>,
+    spl_db: f64,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT OR IGNORE INTO resampled_points (
+            channel_id,
+            frequency_hz,
+            idx,
+            normalized_spl_db,
+            spl_db
+        )
+        VALUES (?, ?, ?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (channel_id, frequency_hz, idx, normalized_spl_db, spl_db);
+    transaction.prepare_cached(query)?.execute(params)?;
+    Ok(())
+}
+
+pub fn select(
+    transaction: &rusqlite::Transaction,
+    channel_id: i32,
+) -> Result<Vec<AveragedPoint>, Error> {
+    let query = indoc!(
+        "
+        SELECT frequency_hz, idx, normalized_spl_db, spl_db
+        FROM resampled_points
+        WHERE channel_id = ?
+        ORDER BY idx
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare_cached(query)?;
+    let points = statement
+        .query_map([channel_id], |row| {
+            Ok(AveragedPoint {
+                frequency_hz: row.get(0)?,
+                idx: row.get(1)?,
+                channel_count: 1,
+                normalized_spl_db: row.get(2)?,
+                spl_db: row.get(3)?,
+            })
+        })?
+        .collect::<Result<Vec<AveragedPoint>, rusqlite::Error>>()?;
+    Ok(points)
+}
+
+pub fn select_curves(transaction: &rusqlite::Transaction) -> Result<Vec<Curve>, Error> {
+    let query = indoc!(
+        "
+        SELECT channels.id, channels.text_hash, resampled_points.idx, resampled_points.spl_db
+        FROM channels
+        LEFT JOIN resampled_points ON resampled_points.channel_id = channels.id
+        ORDER BY channels.id, resampled_points.idx
+        "
+    )
+    .trim_end();
+    let mut statement = transaction.prepare(query)?;
+    let mut rows = statement.query([])?;
+    let mut curves: Vec<Curve> = vec![];
+    while let Some(row) = rows.next()? {
+        let channel_id: i32 = row.get(0)?;
+        if curves.last().map(|curve| curve.channel_id) != Some(channel_id) {
+            curves.push(Curve {
+                channel_id,
+                text_hash: row.get(1)?,
+                start_idx: row.get::<_, Option<i32>>(2)?.unwrap_or(0),
+                spl_db: vec![],
+            });
+        }
+        if let (Some(curve), Some(spl_db)) = (curves.last_mut(), row.get::<_, Option<f64>>(3)?) {
+            curve.spl_db.push(spl_db);
+        }
+    }
+    Ok(curves)
+}
@@ -0,0 +1,91 @@
+use anyhow::Error;
+use indoc::indoc;
+use rusqlite::OptionalExtension;
+
+#[derive(Debug, PartialEq)]
+pub struct TransformOptions {
+    pub compact_points: bool,
+    pub resample_end_hz: f64,
+    pub resample_points_per_octave: u32,
+    pub resample_start_hz: f64,
+    pub smoothing: Option<u32>,
+}
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE TABLE IF NOT EXISTS transform_options (
+            id                         INTEGER PRIMARY KEY,
+            compact_points             INTEGER NOT NULL,
+            resample_end_hz            REAL NOT NULL,
+            resample_points_per_octave INTEGER NOT NULL,
+            resample_start_hz          REAL NOT NULL,
+            smoothing                  INTEGER
+        );
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn delete(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = "DELETE FROM transform_options";
+    transaction.execute(query, [])?;
+    Ok(())
+}
+
+pub fn insert(
+    transaction: &rusqlite::Transaction,
+    transform_options: &TransformOptions,
+) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        INSERT INTO transform_options (
+            compact_points,
+            resample_end_hz,
+            resample_points_per_octave,
+            resample_start_hz,
+            smoothing
+        )
+        VALUES (?, ?, ?, ?, ?)
+        "
+    )
+    .trim_end();
+    let params = (
+        transform_options.compact_points,
+        transform_options.resample_end_hz,
+        transform_options.resample_points_per_octave,
+        transform_options.resample_start_hz,
+        transform_options.smoothing,
+    );
+    transaction.execute(query, params)?;
+    Ok(())
+}
+
+pub fn select(transaction: &rusqlite::Transaction) -> Result<Option<TransformOptions>, Error> {
+    let query = indoc!(
+        "
+        SELECT
+            compact_points,
+            resample_end_hz,
+            resample_points_per_octave,
+            resample_start_hz,
+            smoothing
+        FROM transform_options
+        "
+    )
+    .trim_end();
+    let result = transaction
+        .query_row(query, [], |row| {
+            Ok(TransformOptions {
+                compact_points: row.get(0)?,
+                resample_end_hz: row.get(1)?,
+                resample_points_per_octave: row.get(2)?,
+                resample_start_hz: row.get(3)?,
+                smoothing: row.get(4)?,
+            })
+        })
+        .optional()?;
+    Ok(result)
+}
@@ -0,0 +1,117 @@
+use anyhow::Error;
+use indoc::indoc;
+
+pub fn create(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE VIEW IF NOT EXISTS v_phones AS
+        SELECT
+            phones.id AS phone_id,
+            sites.name AS site_name,
+            sites.username AS site_username,
+            site_dbs.db_types,
+            brands.name AS brand,
+            canonical_brands.name AS canonical_brand,
+            phones.name AS phone,
+            models.name AS model,
+            phones.price,
+            phones.review_score
+        FROM phones
+        JOIN brands ON brands.id = phones.brand_id
+        JOIN sites ON sites.id = brands.site_id
+        LEFT JOIN (
+            SELECT site_id, GROUP_CONCAT(type, ', ') AS db_types
+            FROM dbs
+            GROUP BY site_id
+        ) site_dbs ON site_dbs.site_id = sites.id
+        LEFT JOIN brand_canonical_brands ON brand_canonical_brands.brand_id = brands.id
+        LEFT JOIN canonical_brands
+        ON canonical_brands.id = brand_canonical_brands.canonical_brand_id
+        LEFT JOIN phone_models ON phone_models.phone_id = phones.id
+        LEFT JOIN models ON models.id = phone_models.model_id;
+
+        CREATE VIEW IF NOT EXISTS v_channel_curves AS
+        SELECT
+            channels.id AS channel_id,
+            v_phones.site_username,
+            v_phones.db_types,
+            v_phones.brand,
+            v_phones.canonical_brand,
+            v_phones.phone,
+            v_phones.model,
+            files.text AS variant,
+            COALESCE(channels.type, 'Mono') AS side,
+            channels.idx AS sample_idx,
+            channels.quality,
+            resampled_points.idx,
+            resampled_points.frequency_hz,
+            resampled_points.spl_db,
+            resampled_points.normalized_spl_db
+        FROM channels
+        JOIN files ON files.id = channels.file_id
+        JOIN v_phones ON v_phones.phone_id = files.phone_id
+        JOIN resampled_points ON resampled_points.channel_id = channels.id;
+
+        CREATE VIEW IF NOT EXISTS v_phone_summary AS
+        SELECT
+            v_phones.*,
+            COUNT(DISTINCT files.id) AS file_count,
+            COUNT(DISTINCT channels.file_id || ':' || channels.idx) AS sample_count,
+            COUNT(channels.id) AS channel_count,
+            COUNT(CASE WHEN channels.quality != 'error' THEN 1 END) AS valid_channel_count
+        FROM v_phones
+        LEFT JOIN files ON files.phone_id = v_phones.phone_id
+        LEFT JOIN channels ON channels.file_id = files.id
+        GROUP BY v_phones.phone_id;
+
+        CREATE VIEW IF NOT EXISTS v_site_stats AS
+        SELECT
+            sites.id AS site_id,
+            sites.name AS site_name,
+            sites.username AS site_username,
+            (
+                SELECT GROUP_CONCAT(type, ', ')
+                FROM dbs
+                WHERE dbs.site_id = sites.id
+            ) AS db_types,
+            COUNT(DISTINCT brands.id) AS brand_count,
+            COUNT(DISTINCT phones.id) AS phone_count,
+            COUNT(DISTINCT files.id) AS file_count,
+            COUNT(channels.id) AS channel_count,
+            COUNT(CASE WHEN channels.quality = 'error' THEN 1 END) AS error_channel_count,
+            COUNT(DISTINCT measurement_groups.group_id) AS unique_measurement_count
+        FROM sites
+        LEFT JOIN brands ON brands.site_id = sites.id
+        LEFT JOIN phones ON phones.brand_id = brands.id
+        LEFT JOIN files ON files.phone_id = phones.id
+        LEFT JOIN channels ON channels.file_id = files.id
+        LEFT JOIN measurement_groups ON measurement_groups.channel_id = channels.id
+        GROUP BY sites.id;
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
+
+pub fn create_points(transaction: &rusqlite::Transaction) -> Result<(), Error> {
+    let query = indoc!(
+        "
+        CREATE VIEW IF NOT EXISTS v_points AS
+        SELECT channel_id, frequency_hz, idx, phase_degrees, smoothed_spl_db, spl_db
+        FROM points
+        UNION ALL
+        SELECT
+            point_arrays.channel_id,
+            json_extract(point.value, '$[0]'),
+            point.key,
+            json_extract(point.value, '$[1]'),
+            json_extract(point.value, '$[2]'),
+            json_extract(point.value, '$[3]')
+        FROM point_arrays, json_each(point_arrays.points) AS point;
+        "
+    )
+    .trim_end();
+    transaction.execute_batch(query)?;
+    Ok(())
+}
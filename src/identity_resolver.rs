@@ -0,0 +1,422 @@
+use anyhow::Error;
+use serde::Deserialize;
+use std::collections;
+use std::fs;
+
+const BRAND_SUFFIXES: [&str; 4] = ["acoustics", "audio", "electronics", "technology"];
+
+const MODEL_SIMILARITY: f64 = 0.9;
+
+#[derive(Debug)]
+pub struct Brand {
+    pub id: i64,
+    pub name: String,
+}
+
+#[derive(Debug)]
+pub struct Phone {
+    pub id: i64,
+    pub brand_id: i64,
+    pub name: String,
+}
+
+#[derive(Debug, Default, Deserialize)]
+pub struct Aliases {
+    #[serde(default)]
+    pub brands: collections::BTreeMap<String, String>,
+    #[serde(default)]
+    pub models: Vec<ModelAlias>,
+}
+
+#[derive(Debug, Deserialize)]
+pub struct ModelAlias {
+    pub brand: String,
+    pub name: String,
+    pub model: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Model {
+    pub canonical_brand_idx: usize,
+    pub name: String,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct BrandResolution {
+    pub canonical_brands: Vec<String>,
+    pub brand_canonical_brands: Vec<(i64, usize)>,
+}
+
+#[derive(Debug, PartialEq)]
+pub struct Resolution {
+    pub brands: BrandResolution,
+    pub models: Vec<Model>,
+    pub phone_models: Vec<(i64, usize)>,
+}
+
+struct Cluster<'a> {
+    key: String,
+    phones: Vec<&'a Phone>,
+    alias: Option<&'a str>,
+}
+
+impl Aliases {
+    pub fn load(path: &str) -> Result<Self, Error> {
+        Ok(toml::from_str(&fs::read_to_string(path)?)?)
+    }
+
+    fn brand_key(&self, name: &str) -> String {
+        let key = brand_key(name);
+        self.brands
+            .iter()
+            .find(|(alias, _)| brand_key(alias) == key)
+            .map(|(_, canonical_name)| brand_key(canonical_name))
+            .unwrap_or(key)
+    }
+
+    fn brand_name(&self, key: &str) -> Option<&str> {
+        self.brands
+            .values()
+            .find(|canonical_name| brand_key(canonical_name) == key)
+            .map(|canonical_name| canonical_name.as_str())
+    }
+}
+
+pub fn brand_key(name: &str) -> String {
+    let key: String = name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect();
+    BRAND_SUFFIXES
+        .iter()
+        .find_map(|suffix| {
+            key.strip_suffix(suffix)
+                .filter(|stripped| !stripped.is_empty())
+        })
+        .unwrap_or(&key)
+        .to_string()
+}
+
+pub fn model_key(brand_key: &str, name: &str) -> String {
+    let key: String = name
+        .chars()
+        .filter(|c| c.is_alphanumeric())
+        .flat_map(|c| c.to_lowercase())
+        .collect();
+    match key.strip_prefix(brand_key) {
+        Some(stripped) if !stripped.is_empty() => stripped.to_string(),
+        _ => key,
+    }
+}
+
+pub fn resolve_brands(brands: &[Brand], aliases: &Aliases) -> BrandResolution {
+    let mut keys: collections::BTreeMap<String, Vec<&Brand>> = collections::BTreeMap::new();
+    for brand in brands {
+        keys.entry(aliases.brand_key(&brand.name))
+            .or_default()
+            .push(brand);
+    }
+
+    let mut canonical_brands = vec![];
+    let mut brand_canonical_brands = vec![];
+    for (key, brands) in keys {
+        for brand in &brands {
+            brand_canonical_brands.push((brand.id, canonical_brands.len()));
+        }
+        canonical_brands.push(match aliases.brand_name(&key) {
+            Some(name) => name.to_string(),
+            None => most_common(brands.iter().map(|brand| brand.name.as_str())),
+        });
+    }
+    brand_canonical_brands.sort();
+
+    BrandResolution {
+        canonical_brands,
+        brand_canonical_brands,
+    }
+}
+
+pub fn resolve(brands: &[Brand], phones: &[Phone], aliases: &Aliases) -> Resolution {
+    let brand_resolution = resolve_brands(brands, aliases);
+    let canonical_brand_idxs: collections::HashMap<i64, usize> = brand_resolution
+        .brand_canonical_brands
+        .iter()
+        .copied()
+        .collect();
+    let canonical_brand_keys: Vec<String> = brand_resolution
+        .canonical_brands
+        .iter()
+        .map(|name| aliases.brand_key(name))
+        .collect();
+    let model_aliases: collections::HashMap<(String, String), &str> = aliases
+        .models
+        .iter()
+        .map(|alias| {
+            let brand_key = aliases.brand_key(&alias.brand);
+            let model_key = model_key(&brand_key, &alias.name);
+            ((brand_key, model_key), alias.model.as_str())
+        })
+        .collect();
+
+    let mut canonical_brand_phones: collections::BTreeMap<usize, Vec<&Phone>> =
+        collections::BTreeMap::new();
+    for phone in phones {
+        if let Some(&canonical_brand_idx) = canonical_brand_idxs.get(&phone.brand_id) {
+            canonical_brand_phones
+                .entry(canonical_brand_idx)
+                .or_default()
+                .push(phone);
+        }
+    }
+
+    let mut models = vec![];
+    let mut phone_models = vec![];
+    for (canonical_brand_idx, phones) in canonical_brand_phones {
+        let brand_key = &canonical_brand_keys[canonical_brand_idx];
+        let mut exact_clusters: collections::BTreeMap<String, Cluster> =
+            collections::BTreeMap::new();
+        for phone in phones {
+            let key = model_key(brand_key, &phone.name);
+            let (key, alias) = match model_aliases.get(&(brand_key.clone(), key.clone())) {
+                Some(&model) => (model_key(brand_key, model), Some(model)),
+                None => (key, None),
+            };
+            let cluster = exact_clusters.entry(key.clone()).or_insert(Cluster {
+                key,
+                phones: vec![],
+                alias: None,
+            });
+            cluster.phones.push(phone);
+            cluster.alias = cluster.alias.or(alias);
+        }
+
+        let mut exact_clusters: Vec<Cluster> = exact_clusters.into_values().collect();
+        exact_clusters.sort_by(|a, b| b.phones.len().cmp(&a.phones.len()).then(a.key.cmp(&b.key)));
+        let mut clusters: Vec<Cluster> = vec![];
+        for exact_cluster in exact_clusters {
+            match clusters
+                .iter_mut()
+                .find(|cluster| is_similar(&cluster.key, &exact_cluster.key))
+            {
+                Some(cluster) => {
+                    cluster.phones.extend(exact_cluster.phones);
+                    cluster.alias = cluster.alias.or(exact_cluster.alias);
+                }
+                None => clusters.push(exact_cluster),
+            }
+        }
+
+        let mut brand_models: Vec<(String, Vec<&Phone>)> = clusters
+            .into_iter()
+            .map(|cluster| {
+                let name = match cluster.alias {
+                    Some(alias) => alias.to_string(),
+                    None => most_common(cluster.phones.iter().map(|phone| phone.name.as_str())),
+                };
+                (name, cluster.phones)
+            })
+            .collect();
+        brand_models.sort_by(|a, b| a.0.cmp(&b.0));
+        for (name, phones) in brand_models {
+            for phone in phones {
+                phone_models.push((phone.id, models.len()));
+            }
+            models.push(Model {
+                canonical_brand_idx,
+                name,
+            });
+        }
+    }
+    phone_models.sort();
+
+    Resolution {
+        brands: brand_resolution,
+        models,
+        phone_models,
+    }
+}
+
+fn is_similar(a: &str, b: &str) -> bool {
+    let digits = |key: &str| -> String { key.chars().filter(|c| c.is_numeric()).collect() };
+    a == b || (digits(a) == digits(b) && strsim::normalized_levenshtein(a, b) >= MODEL_SIMILARITY)
+}
+
+fn most_common<'a>(names: impl Iterator<Item = &'a str>) -> String {
+    let mut counts: Vec<(&str, usize)> = vec![];
+    for name in names {
+        match counts
+            .iter_mut()
+            .find(|(counted_name, _)| *counted_name == name)
+        {
+            Some((_, count)) => *count += 1,
+            None => counts.push((name, 1)),
+        }
+    }
+    let is_mixed_case = |name: &str| {
+        name.chars().any(|c| c.is_uppercase()) && name.chars().any(|c| c.is_lowercase())
+    };
+    counts
+        .into_iter()
+        .fold(("", 0, false), |best, (name, count)| {
+            if (count, is_mixed_case(name)) > (best.1, best.2) {
+                (name, count, is_mixed_case(name))
+            } else {
+                best
+            }
+        })
+        .0
+        .to_string()
+}
+
+#[cfg(test)]
+mod tests {
+    use indoc::indoc;
+
+    use super::*;
+
+    fn brands() -> Vec<Brand> {
+        vec![
+            Brand {
+                id: 1,
+                name: "Moondrop".to_string(),
+            },
+            Brand {
+                id: 2,
+                name: "MoonDrop".to_string(),
+            },
+            Brand {
+                id: 3,
+                name: "Moondrop".to_string(),
+            },
+            Brand {
+                id: 4,
+                name: "Truthear Audio".to_string(),
+            },
+            Brand {
+                id: 5,
+                name: "TRUTHEAR".to_string(),
+            },
+            Brand {
+                id: 6,
+                name: "Shuoer".to_string(),
+            },
+        ]
+    }
+
+    fn phone(id: i64, brand_id: i64, name: &str) -> Phone {
+        Phone {
+            id,
+            brand_id,
+            name: name.to_string(),
+        }
+    }
+
+    #[test]
+    fn it_resolves_brand_spelling_variants() {
+        let result = resolve_brands(&brands(), &Aliases::default());
+
+        assert_eq!(
+            result,
+            BrandResolution {
+                canonical_brands: vec![
+                    "Moondrop".to_string(),
+                    "Shuoer".to_string(),
+                    "Truthear Audio".to_string()
+                ],
+                brand_canonical_brands: vec![(1, 0), (2, 0), (3, 0), (4, 2), (5, 2), (6, 1)],
+            }
+        );
+    }
+
+    #[test]
+    fn it_applies_brand_aliases() {
+        let aliases: Aliases = toml::from_str(indoc!(
+            r#"
+            [brands]
+            "Shuoer" = "Letshuoer"
+            "Truthear" = "Truthear"
+            "#
+        ))
+        .unwrap();
+
+        let result = resolve_brands(&brands(), &aliases);
+
+        assert_eq!(
+            result.canonical_brands,
+            vec![
+                "Letshuoer".to_string(),
+                "Moondrop".to_string(),
+                "Truthear".to_string()
+            ]
+        );
+    }
+
+    #[test]
+    fn it_resolves_model_spelling_variants() {
+        let phones = vec![
+            phone(1, 1, "Blessing 2 Dusk"),
+            phone(2, 2, "Blessing2 Dusk"),
+            phone(3, 3, "Moondrop Blessing 2: Dusk"),
+            phone(4, 1, "Blessing 3"),
+            phone(5, 4, "Zero"),
+        ];
+
+        let result = resolve(&brands(), &phones, &Aliases::default());
+
+        assert_eq!(
+            result.models,
+            vec![
+                Model {
+                    canonical_brand_idx: 0,
+                    name: "Blessing 2 Dusk".to_string()
+                },
+                Model {
+                    canonical_brand_idx: 0,
+                    name: "Blessing 3".to_string()
+                },
+                Model {
+                    canonical_brand_idx: 2,
+                    name: "Zero".to_string()
+                },
+            ]
+        );
+        assert_eq!(
+            result.phone_models,
+            vec![(1, 0), (2, 0), (3, 0), (4, 1), (5, 2)]
+        );
+    }
+
+    #[test]
+    fn it_merges_typos_with_the_same_digits() {
+        let phones = vec![
+            phone(1, 1, "Blessing 2 Dusk"),
+            phone(2, 1, "Blessing 2 Dusk"),
+            phone(3, 1, "Blesing 2 Dusk"),
+            phone(4, 1, "Blessing 3 Dusk"),
+        ];
+
+        let result = resolve(&brands(), &phones, &Aliases::default());
+
+        assert_eq!(result.phone_models, vec![(1, 0), (2, 0), (3, 0), (4, 1)]);
+    }
+
+    #[test]
+    fn it_applies_model_aliases() {
+        let phones = vec![phone(1, 1, "Blessing 2 Dusk"), phone(2, 2, "B2 Dusk")];
+        let aliases: Aliases = toml::from_str(indoc!(
+            r#"
+            [[models]]
+            brand = "Moondrop"
+            name = "B2 Dusk"
+            model = "Blessing 2 Dusk"
+            "#
+        ))
+        .unwrap();
+
+        let result = resolve(&brands(), &phones, &aliases);
+
+        assert_eq!(result.models.len(), 1);
+        assert_eq!(result.phone_models, vec![(1, 0), (2, 0)]);
+    }
+}
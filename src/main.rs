@@ -4,7 +4,19 @@
 use anyhow::Error;
 
 mod commands;
+mod hasher;
+mod identity_resolver;
+mod measurement_averager;
+mod measurement_deduplicator;
+mod measurement_normalizer;
 mod measurement_parser;
+mod measurement_resampler;
+mod measurement_smoother;
+mod measurement_summarizer;
+mod measurement_validator;
+mod measurement_writer;
+mod progress_bar;
+mod text_compressor;
 
 #[derive(clap::Parser, Debug)]
 #[command(
@@ -26,9 +38,21 @@ enum Command {
     /// Analyze data stored in the SQLite database
     Analyze(commands::analyze::Command),
 
+    /// Check the SQLite database for orphan rows, missing channels and unparsable measurements
+    Check(commands::check::Command),
+
     /// Download Squiglink data and store it in the SQLite database
     Download(commands::download::Command),
 
+    /// Export averaged curves from the transformed SQLite database as measurement files
+    Export(commands::export::Command),
+
+    /// Upgrade the raw SQLite database to the latest schema without downloading it again
+    Migrate(commands::migrate::Command),
+
+    /// Search phones in the transformed SQLite database
+    Search(commands::search::Command),
+
     /// Transform the SQLite database to simplify analysis from SQL
     Transform(commands::transform::Command),
 }
@@ -37,7 +61,11 @@ fn main() -> Result<(), Error> {
     let arguments: CommandParser = clap::Parser::parse();
     match arguments.command {
         Command::Analyze(command) => command.execute()?,
+        Command::Check(command) => command.execute()?,
         Command::Download(command) => command.execute()?,
+        Command::Export(command) => command.execute()?,
+        Command::Migrate(command) => command.execute()?,
+        Command::Search(command) => command.execute()?,
         Command::Transform(command) => command.execute()?,
     }
 
@@ -0,0 +1,117 @@
+use std::collections;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Domain {
+    Decibel,
+    Power,
+}
+
+#[derive(Clone, Debug)]
+pub struct AveragedPoint {
+    pub frequency_hz: f64,
+    pub idx: i32,
+    pub channel_count: i32,
+    pub normalized_spl_db: Option<f64>,
+    pub spl_db: f64,
+}
+
+pub fn average(curves: &[Vec<AveragedPoint>], domain: Domain) -> Vec<AveragedPoint> {
+    let mut points_by_idx: collections::BTreeMap<i32, Vec<&AveragedPoint>> =
+        collections::BTreeMap::new();
+    for curve in curves {
+        for point in curve {
+            points_by_idx.entry(point.idx).or_default().push(point);
+        }
+    }
+    points_by_idx
+        .into_iter()
+        .map(|(idx, points)| AveragedPoint {
+            frequency_hz: points[0].frequency_hz,
+            idx,
+            channel_count: points.iter().map(|point| point.channel_count).sum(),
+            normalized_spl_db: points
+                .iter()
+                .map(|point| point.normalized_spl_db)
+                .collect::<Option<Vec<f64>>>()
+                .map(|values| mean(&values, domain)),
+            spl_db: mean(
+                &points
+                    .iter()
+                    .map(|point| point.spl_db)
+                    .collect::<Vec<f64>>(),
+                domain,
+            ),
+        })
+        .collect()
+}
+
+fn mean(values_db: &[f64], domain: Domain) -> f64 {
+    match domain {
+        Domain::Decibel => values_db.iter().sum::<f64>() / values_db.len() as f64,
+        Domain::Power => {
+            let power = values_db
+                .iter()
+                .map(|value_db| 10f64.powf(value_db / 10.0))
+                .sum::<f64>()
+                / values_db.len() as f64;
+            10.0 * power.log10()
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn point(idx: i32, normalized_spl_db: Option<f64>, spl_db: f64) -> AveragedPoint {
+        AveragedPoint {
+            frequency_hz: f64::from(idx),
+            idx,
+            channel_count: 1,
+            normalized_spl_db,
+            spl_db,
+        }
+    }
+
+    #[test]
+    fn it_averages_in_decibels() {
+        let curves = vec![
+            vec![point(0, Some(-2.0), 90.0), point(1, Some(0.0), 92.0)],
+            vec![point(1, None, 96.0), point(2, None, 100.0)],
+        ];
+
+        let result = average(&curves, Domain::Decibel);
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|point| (
+                    point.idx,
+                    point.channel_count,
+                    point.normalized_spl_db,
+                    point.spl_db
+                ))
+                .collect::<Vec<(i32, i32, Option<f64>, f64)>>(),
+            vec![
+                (0, 1, Some(-2.0), 90.0),
+                (1, 2, None, 94.0),
+                (2, 1, None, 100.0)
+            ]
+        );
+    }
+
+    #[test]
+    fn it_averages_in_power() {
+        let curves = vec![vec![point(0, None, 90.0)], vec![point(0, None, 90.0)]];
+
+        let result = average(&curves, Domain::Power);
+
+        assert!((result[0].spl_db - 90.0).abs() < 1e-9);
+
+        let curves = vec![vec![point(0, None, 100.0)], vec![point(0, None, 80.0)]];
+
+        let result = average(&curves, Domain::Power);
+
+        assert!((result[0].spl_db - 97.03).abs() < 0.01);
+    }
+}
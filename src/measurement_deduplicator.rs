@@ -0,0 +1,116 @@
+use std::collections;
+
+const NEAR_IDENTICAL_DB: f64 = 0.05;
+
+const BUCKET_DB: f64 = 0.5;
+
+#[derive(Debug)]
+pub struct Curve {
+    pub channel_id: i32,
+    pub text_hash: String,
+    pub start_idx: i32,
+    pub spl_db: Vec<f64>,
+}
+
+pub fn group(curves: &[Curve]) -> Vec<(i32, i32)> {
+    let mut representatives: Vec<usize> = (0..curves.len()).collect();
+    let mut text_hashes: collections::HashMap<&str, usize> = collections::HashMap::new();
+    let mut buckets: collections::HashMap<(i32, usize, i64), Vec<usize>> =
+        collections::HashMap::new();
+    for (index, curve) in curves.iter().enumerate() {
+        if let Some(&other) = text_hashes.get(curve.text_hash.as_str()) {
+            representatives[index] = representatives[other];
+            continue;
+        }
+        text_hashes.insert(&curve.text_hash, index);
+        if curve.spl_db.is_empty() {
+            continue;
+        }
+        let average_spl_db = curve.spl_db.iter().sum::<f64>() / curve.spl_db.len() as f64;
+        let bucket = (average_spl_db / BUCKET_DB).floor() as i64;
+        let representative = [bucket - 1, bucket, bucket + 1]
+            .iter()
+            .filter_map(|neighbour| buckets.get(&(curve.start_idx, curve.spl_db.len(), *neighbour)))
+            .flatten()
+            .copied()
+            .find(|&other| is_near_identical(curve, &curves[other]));
+        match representative {
+            Some(representative) => representatives[index] = representative,
+            None => buckets
+                .entry((curve.start_idx, curve.spl_db.len(), bucket))
+                .or_default()
+                .push(index),
+        }
+    }
+
+    let mut group_ids: collections::HashMap<usize, i32> = collections::HashMap::new();
+    for (index, curve) in curves.iter().enumerate() {
+        let group_id = group_ids
+            .entry(representatives[index])
+            .or_insert(curve.channel_id);
+        *group_id = (*group_id).min(curve.channel_id);
+    }
+    let mut channel_groups: Vec<(i32, i32)> = curves
+        .iter()
+        .enumerate()
+        .map(|(index, curve)| (curve.channel_id, group_ids[&representatives[index]]))
+        .collect();
+    channel_groups.sort();
+    channel_groups
+}
+
+fn is_near_identical(a: &Curve, b: &Curve) -> bool {
+    a.spl_db
+        .iter()
+        .zip(&b.spl_db)
+        .all(|(a, b)| (a - b).abs() <= NEAR_IDENTICAL_DB)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn curve(channel_id: i32, text_hash: &str, spl_db: Vec<f64>) -> Curve {
+        Curve {
+            channel_id,
+            text_hash: text_hash.to_string(),
+            start_idx: 0,
+            spl_db,
+        }
+    }
+
+    #[test]
+    fn it_groups_identical_texts() {
+        let curves = vec![
+            curve(1, "a", vec![90.0, 95.0]),
+            curve(2, "b", vec![80.0, 85.0]),
+            curve(3, "a", vec![90.0, 95.0]),
+        ];
+
+        assert_eq!(group(&curves), vec![(1, 1), (2, 2), (3, 1)]);
+    }
+
+    #[test]
+    fn it_groups_near_identical_curves() {
+        let curves = vec![
+            curve(1, "a", vec![90.0, 95.0]),
+            curve(2, "b", vec![90.02, 94.99]),
+            curve(3, "c", vec![90.0, 95.5]),
+            curve(4, "d", vec![89.74, 94.76]),
+            curve(5, "e", vec![89.76, 94.74]),
+        ];
+
+        assert_eq!(group(&curves), vec![(1, 1), (2, 1), (3, 3), (4, 4), (5, 4)]);
+    }
+
+    #[test]
+    fn it_does_not_chain_near_identical_curves() {
+        let curves = vec![
+            curve(1, "a", vec![90.0, 95.0]),
+            curve(2, "b", vec![90.04, 95.04]),
+            curve(3, "c", vec![90.08, 95.08]),
+        ];
+
+        assert_eq!(group(&curves), vec![(1, 1), (2, 1), (3, 3)]);
+    }
+}
@@ -0,0 +1,133 @@
+use crate::measurement_parser::Point;
+use crate::measurement_resampler;
+
+const BAND_POINTS_PER_OCTAVE: u32 = 48;
+
+#[derive(Debug)]
+pub enum Normalization {
+    None,
+    AtFrequency {
+        frequency_hz: f64,
+    },
+    BandAverage {
+        start_hz: f64,
+        end_hz: f64,
+    },
+    Target {
+        points: Vec<Point>,
+        start_hz: f64,
+        end_hz: f64,
+    },
+}
+
+pub fn offset(points: &[Point], normalization: &Normalization) -> Option<f64> {
+    match normalization {
+        Normalization::None => Some(0.0),
+        Normalization::AtFrequency { frequency_hz } => {
+            measurement_resampler::resample(points, &[*frequency_hz])
+                .first()
+                .map(|point| point.spl_db)
+        }
+        Normalization::BandAverage { start_hz, end_hz } => {
+            let grid = measurement_resampler::grid(*start_hz, *end_hz, BAND_POINTS_PER_OCTAVE);
+            let resampled_points = measurement_resampler::resample(points, &grid);
+            if resampled_points.len() != grid.len() {
+                return None;
+            }
+            Some(
+                resampled_points
+                    .iter()
+                    .map(|point| point.spl_db)
+                    .sum::<f64>()
+                    / resampled_points.len() as f64,
+            )
+        }
+        Normalization::Target {
+            points: target_points,
+            start_hz,
+            end_hz,
+        } => {
+            let grid = measurement_resampler::grid(*start_hz, *end_hz, BAND_POINTS_PER_OCTAVE);
+            let resampled_points = measurement_resampler::resample(points, &grid);
+            let resampled_target_points = measurement_resampler::resample(target_points, &grid);
+            if resampled_points.len() != grid.len() || resampled_target_points.len() != grid.len() {
+                return None;
+            }
+            Some(
+                resampled_points
+                    .iter()
+                    .zip(&resampled_target_points)
+                    .map(|(point, target_point)| point.spl_db - target_point.spl_db)
+                    .sum::<f64>()
+                    / resampled_points.len() as f64,
+            )
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement_parser::tests::point;
+
+    #[test]
+    fn it_normalizes_at_a_frequency() {
+        let points = vec![point(500.0, 90.0), point(2_000.0, 100.0)];
+
+        let result = offset(
+            &points,
+            &Normalization::AtFrequency {
+                frequency_hz: 1_000.0,
+            },
+        );
+
+        assert_eq!(result, Some(95.0));
+    }
+
+    #[test]
+    fn it_normalizes_by_band_average() {
+        let points = vec![point(250.0, 80.0), point(4_000.0, 80.0)];
+
+        let result = offset(
+            &points,
+            &Normalization::BandAverage {
+                start_hz: 300.0,
+                end_hz: 3_000.0,
+            },
+        );
+
+        assert_eq!(result, Some(80.0));
+    }
+
+    #[test]
+    fn it_normalizes_to_a_target() {
+        let points = vec![point(20.0, 100.0), point(20_000.0, 100.0)];
+        let target_points = vec![point(20.0, 90.0), point(20_000.0, 90.0)];
+
+        let result = offset(
+            &points,
+            &Normalization::Target {
+                points: target_points,
+                start_hz: 300.0,
+                end_hz: 3_000.0,
+            },
+        );
+
+        assert_eq!(result, Some(10.0));
+    }
+
+    #[test]
+    fn it_skips_curves_outside_the_band() {
+        let points = vec![point(1_000.0, 80.0), point(20_000.0, 80.0)];
+
+        let result = offset(
+            &points,
+            &Normalization::BandAverage {
+                start_hz: 300.0,
+                end_hz: 3_000.0,
+            },
+        );
+
+        assert_eq!(result, None);
+    }
+}
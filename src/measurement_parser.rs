@@ -1,4 +1,4 @@
-use anyhow::Error;
+use anyhow::{ensure, Context, Error};
 
 #[derive(Debug)]
 pub struct Point {
@@ -7,9 +7,17 @@ pub struct Point {
     pub spl_db: f64,
 }
 
+#[derive(Debug)]
+pub struct ImpedancePoint {
+    pub frequency_hz: f64,
+    pub impedance_ohms: f64,
+    pub phase_degrees: Option<f64>,
+}
+
 #[derive(Debug)]
 enum Kind {
     AchoReviews,
+    Arta,
     Comment,
     FftAudioTools,
     FrequencyMagnitude,
@@ -22,8 +30,33 @@ enum Kind {
 }
 
 pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
+    ensure!(
+        !is_impedance(text),
+        "expected a frequency response, found an impedance curve"
+    );
+    parse_points(text)
+}
+
+pub fn parse_impedance(text: &str) -> Result<Vec<ImpedancePoint>, Error> {
+    let points = parse_points(text)?
+        .into_iter()
+        .map(|point| ImpedancePoint {
+            frequency_hz: point.frequency_hz,
+            impedance_ohms: point.spl_db,
+            phase_degrees: point.phase_degrees,
+        })
+        .collect();
+    Ok(points)
+}
+
+pub fn is_impedance(text: &str) -> bool {
+    text.lines().any(|line| arta_program(line) == Some("LIMP"))
+}
+
+fn parse_points(text: &str) -> Result<Vec<Point>, Error> {
     let mut points = vec![];
     let mut kind = Kind::Unknown;
+    let decimal_comma = uses_decimal_comma(text);
 
     for line in text.lines() {
         if line == "* +info = www.achoreviews.com/solospec" || line == "+info = www.achoreviews.com"
@@ -31,6 +64,10 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
             kind = Kind::AchoReviews;
             continue;
         }
+        if arta_program(line).is_some() {
+            kind = Kind::Arta;
+            continue;
+        }
         if line == "Comment: TJ Comment\t" {
             kind = Kind::Comment;
             continue;
@@ -69,6 +106,7 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
             || line == "Senny IE600 L.txt"
             || line == "Senny IE600 R.txt"
             || line.is_empty()
+            || line.starts_with('"')
             || line.starts_with('*')
             || line.starts_with("averaging")
             || line.starts_with("decay")
@@ -82,13 +120,29 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
             continue;
         }
 
+        let line = if decimal_comma {
+            line.replace(',', ".")
+        } else {
+            line.to_string()
+        };
         match kind {
+            Kind::Arta => {
+                if !line.starts_with(|c: char| c.is_ascii_digit()) {
+                    continue;
+                }
+                let values: Vec<String> = line.split_whitespace().map(|x| x.to_string()).collect();
+                points.push(Point {
+                    frequency_hz: parse_value(&values, 0)?,
+                    phase_degrees: values.get(2).and_then(|value| value.parse::<f64>().ok()),
+                    spl_db: parse_value(&values, 1)?,
+                });
+            }
             Kind::AchoReviews => {
                 let values: Vec<String> = line.split('\t').map(|x| x.trim().to_string()).collect();
                 points.push(Point {
-                    frequency_hz: values[0].parse::<f64>()?,
-                    phase_degrees: values[2].parse::<f64>().ok(),
-                    spl_db: values[1].parse::<f64>()?,
+                    frequency_hz: parse_value(&values, 0)?,
+                    phase_degrees: values.get(2).and_then(|value| value.parse::<f64>().ok()),
+                    spl_db: parse_value(&values, 1)?,
                 });
             }
             Kind::Comment
@@ -99,9 +153,9 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
             | Kind::XRight => {
                 let values: Vec<String> = line.split('\t').map(|x| x.trim().to_string()).collect();
                 points.push(Point {
-                    frequency_hz: values[0].parse::<f64>()?,
+                    frequency_hz: parse_value(&values, 0)?,
                     phase_degrees: None,
-                    spl_db: values[1].parse::<f64>()?,
+                    spl_db: parse_value(&values, 1)?,
                 });
             }
             Kind::FrequencySplPhase => {
@@ -112,9 +166,9 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
                     .map(|x| x.trim().to_string())
                     .collect();
                 points.push(Point {
-                    frequency_hz: values[0].parse::<f64>()?,
-                    phase_degrees: values[2].parse::<f64>().ok(),
-                    spl_db: values[1].parse::<f64>()?,
+                    frequency_hz: parse_value(&values, 0)?,
+                    phase_degrees: values.get(2).and_then(|value| value.parse::<f64>().ok()),
+                    spl_db: parse_value(&values, 1)?,
                 });
             }
             Kind::RewV5 => {
@@ -122,6 +176,8 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
                     ", "
                 } else if line.contains("; ") {
                     "; "
+                } else if line.contains(';') {
+                    ";"
                 } else if line.contains('\t') {
                     "\t"
                 } else {
@@ -132,14 +188,16 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
                     .map(|x| x.trim().to_string())
                     .collect();
                 points.push(Point {
-                    frequency_hz: values[0].parse::<f64>()?,
+                    frequency_hz: parse_value(&values, 0)?,
                     phase_degrees: values.get(2).and_then(|value| value.parse::<f64>().ok()),
-                    spl_db: values[1].parse::<f64>()?,
+                    spl_db: parse_value(&values, 1)?,
                 });
             }
             Kind::Unknown => {
                 let separator = if line.contains(',') {
                     ","
+                } else if line.contains(';') {
+                    ";"
                 } else if line.contains('\t') {
                     "\t"
                 } else {
@@ -147,12 +205,13 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
                 };
                 let values: Vec<String> = line
                     .split(separator)
+                    .filter(|x| !x.is_empty())
                     .map(|x| x.trim().to_string())
                     .collect();
                 points.push(Point {
-                    frequency_hz: values[0].parse::<f64>()?,
+                    frequency_hz: parse_value(&values, 0)?,
                     phase_degrees: values.get(2).and_then(|value| value.parse::<f64>().ok()),
-                    spl_db: values[1].parse::<f64>()?,
+                    spl_db: parse_value(&values, 1)?,
                 });
             }
         }
@@ -161,12 +220,60 @@ pub fn parse(text: &str) -> Result<Vec<Point>, Error> {
     Ok(points)
 }
 
+pub fn parse_smoothing(text: &str) -> Option<u32> {
+    text.lines()
+        .find_map(|line| {
+            line.strip_prefix("* Smoothing:")
+                .or_else(|| line.strip_prefix("averaging"))
+        })
+        .and_then(|value| value.trim().strip_prefix("1/"))
+        .and_then(|value| value.split_whitespace().next())
+        .and_then(|value| value.parse::<u32>().ok())
+}
+
+fn arta_program(line: &str) -> Option<&str> {
+    let (program, title) = line.split_once(' ')?;
+    let is_title = title.starts_with(|c: char| c.is_ascii_alphabetic())
+        && !title.contains(|c: char| c.is_ascii_digit());
+    ["ARTA", "LIMP", "STEPS"]
+        .into_iter()
+        .find(|name| *name == program && is_title)
+}
+
+fn parse_value(values: &[String], index: usize) -> Result<f64, Error> {
+    let value = values
+        .get(index)
+        .with_context(|| format!("expected at least {} columns", index + 1))?;
+    Ok(value.parse::<f64>()?)
+}
+
+fn uses_decimal_comma(text: &str) -> bool {
+    let data_lines: Vec<&str> = text
+        .lines()
+        .map(|line| line.trim())
+        .filter(|line| line.starts_with(|c: char| c.is_ascii_digit()))
+        .collect();
+    data_lines.iter().any(|line| line.contains(','))
+        && data_lines
+            .iter()
+            .all(|line| !line.contains('.') && (line.contains(';') || line.contains('\t')))
+}
+
 #[cfg(test)]
-mod tests {
+pub mod tests {
     use indoc::indoc;
+    use proptest::prelude::*;
 
     use super::*;
 
+    pub fn point(frequency_hz: f64, spl_db: f64) -> Point {
+        Point {
+            frequency_hz,
+            phase_degrees: None,
+            spl_db,
+        }
+    }
+
     impl PartialEq for Point {
         fn eq(&self, other: &Self) -> bool {
             self.frequency_hz == other.frequency_hz
@@ -175,6 +282,26 @@ mod tests {
         }
     }
 
+    impl PartialEq for ImpedancePoint {
+        fn eq(&self, other: &Self) -> bool {
+            self.frequency_hz == other.frequency_hz
+                && self.impedance_ohms == other.impedance_ohms
+                && self.phase_degrees == other.phase_degrees
+        }
+    }
+
+    #[test]
+    fn it_fails_on_single_column_lines() {
+        let result = parse(indoc!(
+            "
+            x\tLEFT
+            20.0527
+            "
+        ));
+
+        assert!(result.is_err());
+    }
+
     #[test]
     fn it_parses_acho_reviews() {
         let result = parse(indoc!(
@@ -235,6 +362,95 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_parses_arta() {
+        let result = parse(indoc!(
+            "
+            ARTA Frequency Response
+            Freq (Hz)\tMag (dB)\tPhase (deg)
+            20.0507\t84.5432\t-12.9711
+            20.3453\t84.5601\t-13.1543
+            "
+        ))
+        .unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                Point {
+                    frequency_hz: 20.0507,
+                    phase_degrees: Some(-12.9711),
+                    spl_db: 84.5432
+                },
+                Point {
+                    frequency_hz: 20.3453,
+                    phase_degrees: Some(-13.1543),
+                    spl_db: 84.5601
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_decimal_comma_with_semicolons() {
+        let result = parse(indoc!(
+            "
+            * Measurement data measured by REW V5.20.13
+            * Smoothing: None
+            * Frequency Step: 1/48 octave
+            * Start Frequency: 20,000 Hz
+            *
+            * Freq(Hz);SPL(dB);Phase(degrees)
+            20,000000;96,774;36,7401
+            20,299999;96,813;36,0714
+            "
+        ))
+        .unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                Point {
+                    frequency_hz: 20.000000,
+                    phase_degrees: Some(36.7401),
+                    spl_db: 96.774
+                },
+                Point {
+                    frequency_hz: 20.299999,
+                    phase_degrees: Some(36.0714),
+                    spl_db: 96.813
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn it_parses_decimal_comma_with_tabs() {
+        let result = parse(indoc!(
+            "
+            20,000000\t96,774
+            20,299999\t96,813
+            "
+        ))
+        .unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                Point {
+                    frequency_hz: 20.000000,
+                    phase_degrees: None,
+                    spl_db: 96.774
+                },
+                Point {
+                    frequency_hz: 20.299999,
+                    phase_degrees: None,
+                    spl_db: 96.813
+                }
+            ]
+        );
+    }
+
     #[test]
     fn it_parses_fft_audio_tools() {
         let result = parse(indoc!(
@@ -272,6 +488,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn it_parses_frd() {
+        let result = parse(indoc!(
+            "
+            \"Freq\"  \"SPL\"  \"Phase\"
+            10.000   78.52   -3.57
+            10.116   78.56   -3.91
+            "
+        ))
+        .unwrap();
+
+        assert_eq!(
+            result,
+            vec![
+                Point {
+                    frequency_hz: 10.000,
+                    phase_degrees: Some(-3.57),
+                    spl_db: 78.52
+                },
+                Point {
+                    frequency_hz: 10.116,
+                    phase_degrees: Some(-3.91),
+                    spl_db: 78.56
+                }
+            ]
+        );
+    }
+
     #[test]
     fn it_parses_frequency_magnitude() {
         let result = parse(indoc!(
@@ -521,4 +765,67 @@ mod tests {
             ]
         );
     }
+
+    #[test]
+    fn it_parses_smoothing() {
+        assert_eq!(parse_smoothing("* Smoothing: 1/12 octave\n"), Some(12));
+        assert_eq!(parse_smoothing("averaging\t1/24 Octave\n"), Some(24));
+        assert_eq!(parse_smoothing("* Smoothing: None\n"), None);
+        assert_eq!(parse_smoothing("20.0\t96.774\n"), None);
+    }
+
+    #[test]
+    fn it_parses_zma() {
+        let text = indoc!(
+            "
+            LIMP Impedance Magnitude and Phase
+            Freq (Hz)\tMag (Ohm)\tPhase (deg)
+            10.000\t16.834\t42.117
+            10.116\t16.952\t42.301
+            "
+        );
+
+        assert!(is_impedance(text));
+        assert!(parse(text).is_err());
+        assert_eq!(
+            parse_impedance(text).unwrap(),
+            vec![
+                ImpedancePoint {
+                    frequency_hz: 10.000,
+                    impedance_ohms: 16.834,
+                    phase_degrees: Some(42.117)
+                },
+                ImpedancePoint {
+                    frequency_hz: 10.116,
+                    impedance_ohms: 16.952,
+                    phase_degrees: Some(42.301)
+                }
+            ]
+        );
+    }
+
+    #[test]
+    fn it_detects_arta_headers_only() {
+        assert_eq!(arta_program("ARTA Frequency Response"), Some("ARTA"));
+        assert_eq!(arta_program("STEPS Frequency Response"), Some("STEPS"));
+        assert_eq!(
+            arta_program("LIMP Impedance Magnitude and Phase"),
+            Some("LIMP")
+        );
+        assert_eq!(arta_program("ARTAX Frequency Response"), None);
+        assert_eq!(arta_program("ARTA 20 90"), None);
+        assert_eq!(arta_program("ARTA"), None);
+    }
+
+    proptest! {
+        #[test]
+        fn it_never_panics(text in "\\PC*") {
+            let _ = parse(&text);
+        }
+
+        #[test]
+        fn it_never_panics_on_number_like_lines(text in "([0-9.,;\t -]{0,24}\n){0,8}") {
+            let _ = parse(&text);
+        }
+    }
 }
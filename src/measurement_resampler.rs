@@ -0,0 +1,89 @@
+use crate::measurement_parser::Point;
+
+#[derive(Debug)]
+pub struct ResampledPoint {
+    pub frequency_hz: f64,
+    pub idx: usize,
+    pub spl_db: f64,
+}
+
+pub fn grid(start_hz: f64, end_hz: f64, points_per_octave: u32) -> Vec<f64> {
+    let mut frequencies = vec![];
+    let mut index = 0;
+    loop {
+        let frequency_hz = start_hz * 2f64.powf(f64::from(index) / f64::from(points_per_octave));
+        if frequency_hz > end_hz * (1.0 + 1e-9) {
+            break;
+        }
+        frequencies.push(frequency_hz);
+        index += 1;
+    }
+    frequencies
+}
+
+pub fn resample(points: &[Point], grid: &[f64]) -> Vec<ResampledPoint> {
+    let mut points: Vec<&Point> = points
+        .iter()
+        .filter(|point| {
+            point.frequency_hz.is_finite() && point.frequency_hz > 0.0 && point.spl_db.is_finite()
+        })
+        .collect();
+    points.sort_by(|a, b| a.frequency_hz.total_cmp(&b.frequency_hz));
+    points.dedup_by(|a, b| a.frequency_hz == b.frequency_hz);
+
+    let mut resampled_points = vec![];
+    let mut upper = 0;
+    for (idx, &frequency_hz) in grid.iter().enumerate() {
+        while upper < points.len() && points[upper].frequency_hz < frequency_hz {
+            upper += 1;
+        }
+        if upper == points.len() {
+            break;
+        }
+        let spl_db = if points[upper].frequency_hz == frequency_hz {
+            points[upper].spl_db
+        } else if upper == 0 {
+            continue;
+        } else {
+            let lower = points[upper - 1];
+            let upper = points[upper];
+            let ratio = (frequency_hz / lower.frequency_hz).ln()
+                / (upper.frequency_hz / lower.frequency_hz).ln();
+            lower.spl_db + ratio * (upper.spl_db - lower.spl_db)
+        };
+        resampled_points.push(ResampledPoint {
+            frequency_hz,
+            idx,
+            spl_db,
+        });
+    }
+    resampled_points
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement_parser::tests::point;
+
+    #[test]
+    fn it_builds_a_log_spaced_grid() {
+        let result = grid(20.0, 160.0, 1);
+
+        assert_eq!(result, vec![20.0, 40.0, 80.0, 160.0]);
+    }
+
+    #[test]
+    fn it_interpolates_in_log_frequency() {
+        let points = vec![point(80.0, 70.0), point(20.0, 90.0)];
+
+        let result = resample(&points, &grid(10.0, 160.0, 1));
+
+        assert_eq!(
+            result
+                .iter()
+                .map(|point| (point.idx, point.frequency_hz, point.spl_db))
+                .collect::<Vec<_>>(),
+            vec![(1, 20.0, 90.0), (2, 40.0, 80.0), (3, 80.0, 70.0)]
+        );
+    }
+}
@@ -0,0 +1,93 @@
+use crate::measurement_parser::Point;
+
+pub fn smooth(points: &[Point], fraction: u32) -> Vec<f64> {
+    let half_bandwidth = 2f64.powf(1.0 / (2.0 * f64::from(fraction)));
+    let mut order: Vec<usize> = (0..points.len())
+        .filter(|&index| {
+            let point = &points[index];
+            point.frequency_hz.is_finite() && point.frequency_hz > 0.0 && point.spl_db.is_finite()
+        })
+        .collect();
+    order.sort_by(|&a, &b| points[a].frequency_hz.total_cmp(&points[b].frequency_hz));
+
+    let mut smoothed_spl_db: Vec<f64> = points.iter().map(|point| point.spl_db).collect();
+    let mut lower = 0;
+    let mut upper = 0;
+    let mut sum = 0.0;
+    for &index in &order {
+        let frequency_hz = points[index].frequency_hz;
+        while upper < order.len()
+            && points[order[upper]].frequency_hz <= frequency_hz * half_bandwidth
+        {
+            sum += points[order[upper]].spl_db;
+            upper += 1;
+        }
+        while points[order[lower]].frequency_hz < frequency_hz / half_bandwidth {
+            sum -= points[order[lower]].spl_db;
+            lower += 1;
+        }
+        smoothed_spl_db[index] = sum / (upper - lower) as f64;
+    }
+    smoothed_spl_db
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement_parser::tests::point;
+
+    #[test]
+    fn it_averages_within_the_bandwidth() {
+        let points = vec![
+            point(100.0, 80.0),
+            point(1_000.0, 90.0),
+            point(1_100.0, 96.0),
+            point(10_000.0, 70.0),
+        ];
+
+        let result = smooth(&points, 3);
+
+        assert_eq!(result, vec![80.0, 93.0, 93.0, 70.0]);
+    }
+
+    #[test]
+    fn it_keeps_narrow_features_with_fine_smoothing() {
+        let points = vec![point(1_000.0, 90.0), point(1_100.0, 96.0)];
+
+        let result = smooth(&points, 48);
+
+        assert_eq!(result, vec![90.0, 96.0]);
+    }
+
+    #[test]
+    fn it_keeps_invalid_frequencies_unsmoothed() {
+        let points = vec![
+            point(-1_000.0, 80.0),
+            point(0.0, 85.0),
+            point(f64::NAN, 90.0),
+            point(1_000.0, 90.0),
+            point(1_100.0, 96.0),
+        ];
+
+        let result = smooth(&points, 3);
+
+        assert_eq!(result, vec![80.0, 85.0, 90.0, 93.0, 93.0]);
+    }
+
+    #[test]
+    fn it_leaves_non_finite_levels_out_of_the_average() {
+        let points = vec![
+            point(1_000.0, 90.0),
+            point(1_050.0, f64::NAN),
+            point(1_100.0, 96.0),
+            point(1_150.0, f64::INFINITY),
+        ];
+
+        let result = smooth(&points, 3);
+
+        assert_eq!(result[0], 93.0);
+        assert!(result[1].is_nan());
+        assert_eq!(result[2], 93.0);
+        assert_eq!(result[3], f64::INFINITY);
+    }
+}
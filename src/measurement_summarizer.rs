@@ -0,0 +1,202 @@
+use crate::measurement_parser::Point;
+use crate::measurement_resampler;
+use crate::measurement_smoother;
+
+const POINTS_PER_OCTAVE: u32 = 48;
+
+const BAND_TOLERANCE: f64 = 1.122_462_048_309_373;
+
+const EAR_GAIN_REFERENCE_HZ: f64 = 1_000.0;
+
+const EAR_GAIN_START_HZ: f64 = 1_500.0;
+
+const EAR_GAIN_END_HZ: f64 = 5_000.0;
+
+const TREBLE_START_HZ: f64 = 5_000.0;
+
+const TREBLE_TREND_FRACTION: u32 = 1;
+
+#[derive(Debug, Default, PartialEq)]
+pub struct Metrics {
+    pub air_db: Option<f64>,
+    pub bass_db: Option<f64>,
+    pub ear_gain_db: Option<f64>,
+    pub ear_gain_frequency_hz: Option<f64>,
+    pub low_mid_db: Option<f64>,
+    pub max_frequency_hz: Option<f64>,
+    pub mid_db: Option<f64>,
+    pub min_frequency_hz: Option<f64>,
+    pub presence_db: Option<f64>,
+    pub sub_bass_db: Option<f64>,
+    pub tilt_db_per_octave: Option<f64>,
+    pub treble_db: Option<f64>,
+    pub treble_dip_db: Option<f64>,
+    pub treble_dip_frequency_hz: Option<f64>,
+    pub treble_peak_db: Option<f64>,
+    pub treble_peak_frequency_hz: Option<f64>,
+    pub upper_mid_db: Option<f64>,
+}
+
+pub fn summarize(points: &[Point]) -> Metrics {
+    let grid = measurement_resampler::grid(20.0, 20_000.0, POINTS_PER_OCTAVE);
+    let resampled_points = measurement_resampler::resample(points, &grid);
+    let (Some(first_point), Some(last_point)) = (resampled_points.first(), resampled_points.last())
+    else {
+        return Metrics::default();
+    };
+    let band_db = |start_hz: f64, end_hz: f64| {
+        if first_point.frequency_hz > start_hz * BAND_TOLERANCE
+            || last_point.frequency_hz < end_hz / BAND_TOLERANCE
+        {
+            return None;
+        }
+        let values: Vec<f64> = resampled_points
+            .iter()
+            .filter(|point| start_hz <= point.frequency_hz && point.frequency_hz < end_hz)
+            .map(|point| point.spl_db)
+            .collect();
+        (!values.is_empty()).then(|| values.iter().sum::<f64>() / values.len() as f64)
+    };
+
+    let ear_gain_peak = measurement_resampler::resample(points, &[EAR_GAIN_REFERENCE_HZ])
+        .first()
+        .and_then(|reference_point| {
+            resampled_points
+                .iter()
+                .filter(|point| {
+                    EAR_GAIN_START_HZ <= point.frequency_hz && point.frequency_hz <= EAR_GAIN_END_HZ
+                })
+                .max_by(|a, b| a.spl_db.total_cmp(&b.spl_db))
+                .map(|point| (point.frequency_hz, point.spl_db - reference_point.spl_db))
+        });
+
+    let trend_points: Vec<Point> = resampled_points
+        .iter()
+        .map(|point| Point {
+            frequency_hz: point.frequency_hz,
+            phase_degrees: None,
+            spl_db: point.spl_db,
+        })
+        .collect();
+    let trend_spl_db = measurement_smoother::smooth(&trend_points, TREBLE_TREND_FRACTION);
+    let treble_deviations: Vec<(f64, f64)> = resampled_points
+        .iter()
+        .zip(&trend_spl_db)
+        .filter(|(point, _)| point.frequency_hz >= TREBLE_START_HZ)
+        .map(|(point, trend_spl_db)| (point.frequency_hz, point.spl_db - trend_spl_db))
+        .collect();
+    let treble_peak = treble_deviations
+        .iter()
+        .max_by(|a, b| a.1.total_cmp(&b.1))
+        .copied();
+    let treble_dip = treble_deviations
+        .iter()
+        .min_by(|a, b| a.1.total_cmp(&b.1))
+        .copied();
+
+    Metrics {
+        air_db: band_db(10_000.0, 20_000.0),
+        bass_db: band_db(60.0, 250.0),
+        ear_gain_db: ear_gain_peak.map(|(_, spl_db)| spl_db),
+        ear_gain_frequency_hz: ear_gain_peak.map(|(frequency_hz, _)| frequency_hz),
+        low_mid_db: band_db(250.0, 500.0),
+        max_frequency_hz: points
+            .iter()
+            .map(|point| point.frequency_hz)
+            .max_by(f64::total_cmp),
+        mid_db: band_db(500.0, 2_000.0),
+        min_frequency_hz: points
+            .iter()
+            .map(|point| point.frequency_hz)
+            .min_by(f64::total_cmp),
+        presence_db: band_db(4_000.0, 6_000.0),
+        sub_bass_db: band_db(20.0, 60.0),
+        tilt_db_per_octave: tilt(&resampled_points),
+        treble_db: band_db(6_000.0, 10_000.0),
+        treble_dip_db: treble_dip.map(|(_, spl_db)| spl_db),
+        treble_dip_frequency_hz: treble_dip.map(|(frequency_hz, _)| frequency_hz),
+        treble_peak_db: treble_peak.map(|(_, spl_db)| spl_db),
+        treble_peak_frequency_hz: treble_peak.map(|(frequency_hz, _)| frequency_hz),
+        upper_mid_db: band_db(2_000.0, 4_000.0),
+    }
+}
+
+fn tilt(resampled_points: &[measurement_resampler::ResampledPoint]) -> Option<f64> {
+    if resampled_points.len() < 2 {
+        return None;
+    }
+    let len = resampled_points.len() as f64;
+    let octaves: Vec<f64> = resampled_points
+        .iter()
+        .map(|point| point.frequency_hz.log2())
+        .collect();
+    let mean_octaves = octaves.iter().sum::<f64>() / len;
+    let mean_spl_db = resampled_points
+        .iter()
+        .map(|point| point.spl_db)
+        .sum::<f64>()
+        / len;
+    let covariance = octaves
+        .iter()
+        .zip(resampled_points)
+        .map(|(octaves, point)| (octaves - mean_octaves) * (point.spl_db - mean_spl_db))
+        .sum::<f64>();
+    let variance = octaves
+        .iter()
+        .map(|octaves| (octaves - mean_octaves).powi(2))
+        .sum::<f64>();
+    Some(covariance / variance)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement_parser::tests::point;
+
+    #[test]
+    fn it_measures_the_tilt() {
+        let points = vec![point(20.0, 100.0), point(20_480.0, 90.0)];
+
+        let result = summarize(&points);
+
+        assert!((result.tilt_db_per_octave.unwrap() + 1.0).abs() < 1e-9);
+        assert!((result.mid_db.unwrap() - (100.0 - 50f64.log2())).abs() < 0.02);
+        assert_eq!(result.min_frequency_hz, Some(20.0));
+        assert_eq!(result.max_frequency_hz, Some(20_480.0));
+    }
+
+    #[test]
+    fn it_finds_the_ear_gain_peak_and_treble_peaks() {
+        let points = vec![
+            point(20.0, 90.0),
+            point(1_000.0, 90.0),
+            point(3_000.0, 100.0),
+            point(6_000.0, 90.0),
+            point(8_000.0, 96.0),
+            point(9_000.0, 90.0),
+            point(12_000.0, 80.0),
+            point(14_000.0, 90.0),
+            point(20_000.0, 90.0),
+        ];
+
+        let result = summarize(&points);
+
+        assert!((result.ear_gain_frequency_hz.unwrap() - 3_000.0).abs() < 50.0);
+        assert!((result.ear_gain_db.unwrap() - 10.0).abs() < 0.1);
+        assert!((result.treble_peak_frequency_hz.unwrap() - 8_000.0).abs() < 100.0);
+        assert!((result.treble_dip_frequency_hz.unwrap() - 12_000.0).abs() < 200.0);
+        assert!(result.treble_peak_db.unwrap() > 0.0);
+        assert!(result.treble_dip_db.unwrap() < 0.0);
+    }
+
+    #[test]
+    fn it_skips_bands_outside_the_curve() {
+        let points = vec![point(200.0, 90.0), point(20_000.0, 90.0)];
+
+        let result = summarize(&points);
+
+        assert_eq!(result.sub_bass_db, None);
+        assert_eq!(result.bass_db, None);
+        assert_eq!(result.low_mid_db, Some(90.0));
+    }
+}
@@ -0,0 +1,249 @@
+use crate::measurement_parser::Point;
+
+const COVERAGE_START_HZ: f64 = 20.0;
+
+const COVERAGE_END_HZ: f64 = 20_000.0;
+
+const COVERAGE_TOLERANCE: f64 = 1.122_462_048_309_373;
+
+const FLAT_LINE_SPREAD_DB: f64 = 1.0;
+
+#[derive(Debug, PartialEq)]
+pub enum Issue {
+    DescendingFrequency,
+    DuplicateFrequency,
+    FlatLine,
+    ImpedanceCurve,
+    InsufficientCoverage,
+    MissingPhase,
+    NegativeFrequency,
+    NonFiniteValue,
+    NoPoints,
+    Unparsable,
+    ZeroSpl,
+}
+
+#[derive(Debug, Eq, Ord, PartialEq, PartialOrd)]
+pub enum Severity {
+    Info,
+    Warning,
+    Error,
+}
+
+#[derive(Debug, PartialEq)]
+pub enum Quality {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl Issue {
+    pub fn severity(&self) -> Severity {
+        match self {
+            Issue::DescendingFrequency
+            | Issue::DuplicateFrequency
+            | Issue::ImpedanceCurve
+            | Issue::NegativeFrequency
+            | Issue::NonFiniteValue
+            | Issue::NoPoints
+            | Issue::Unparsable
+            | Issue::ZeroSpl => Severity::Error,
+            Issue::FlatLine | Issue::InsufficientCoverage => Severity::Warning,
+            Issue::MissingPhase => Severity::Info,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Issue::DescendingFrequency => "descending-frequency",
+            Issue::DuplicateFrequency => "duplicate-frequency",
+            Issue::FlatLine => "flat-line",
+            Issue::ImpedanceCurve => "impedance-curve",
+            Issue::InsufficientCoverage => "insufficient-coverage",
+            Issue::MissingPhase => "missing-phase",
+            Issue::NegativeFrequency => "negative-frequency",
+            Issue::NonFiniteValue => "non-finite-value",
+            Issue::NoPoints => "no-points",
+            Issue::Unparsable => "unparsable",
+            Issue::ZeroSpl => "zero-spl",
+        }
+    }
+}
+
+impl Severity {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Severity::Info => "info",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+impl Quality {
+    pub fn from_issues(issues: &[Issue]) -> Self {
+        match issues.iter().map(|issue| issue.severity()).max() {
+            Some(Severity::Error) => Quality::Error,
+            Some(Severity::Warning) => Quality::Warning,
+            Some(Severity::Info) | None => Quality::Ok,
+        }
+    }
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            Quality::Ok => "ok",
+            Quality::Warning => "warning",
+            Quality::Error => "error",
+        }
+    }
+}
+
+pub fn quality(points: &[Point]) -> Quality {
+    Quality::from_issues(&validate(points))
+}
+
+pub fn validate(points: &[Point]) -> Vec<Issue> {
+    let mut issues = vec![];
+    if points.is_empty() {
+        issues.push(Issue::NoPoints);
+        return issues;
+    }
+
+    if points.iter().any(|point| {
+        !point.frequency_hz.is_finite()
+            || !point.spl_db.is_finite()
+            || point.phase_degrees.is_some_and(|phase| !phase.is_finite())
+    }) {
+        issues.push(Issue::NonFiniteValue);
+    }
+    if points.iter().any(|point| point.frequency_hz < 0.0) {
+        issues.push(Issue::NegativeFrequency);
+    }
+    if points.iter().any(|point| point.spl_db == 0.0) {
+        issues.push(Issue::ZeroSpl);
+    }
+    if points
+        .windows(2)
+        .any(|pair| pair[1].frequency_hz == pair[0].frequency_hz)
+    {
+        issues.push(Issue::DuplicateFrequency);
+    }
+    if points
+        .windows(2)
+        .any(|pair| pair[1].frequency_hz < pair[0].frequency_hz)
+    {
+        issues.push(Issue::DescendingFrequency);
+    }
+
+    let frequency_hz_min = points
+        .iter()
+        .map(|point| point.frequency_hz)
+        .fold(f64::INFINITY, f64::min);
+    let frequency_hz_max = points
+        .iter()
+        .map(|point| point.frequency_hz)
+        .fold(f64::NEG_INFINITY, f64::max);
+    if frequency_hz_min > COVERAGE_START_HZ * COVERAGE_TOLERANCE
+        || frequency_hz_max < COVERAGE_END_HZ / COVERAGE_TOLERANCE
+    {
+        issues.push(Issue::InsufficientCoverage);
+    }
+    let spl_db_min = points
+        .iter()
+        .map(|point| point.spl_db)
+        .fold(f64::INFINITY, f64::min);
+    let spl_db_max = points
+        .iter()
+        .map(|point| point.spl_db)
+        .fold(f64::NEG_INFINITY, f64::max);
+    if spl_db_max - spl_db_min < FLAT_LINE_SPREAD_DB {
+        issues.push(Issue::FlatLine);
+    }
+    if points.iter().all(|point| point.phase_degrees.is_none()) {
+        issues.push(Issue::MissingPhase);
+    }
+
+    issues
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::measurement_parser::tests::point;
+
+    fn phased_point(frequency_hz: f64, spl_db: f64) -> Point {
+        Point {
+            phase_degrees: Some(0.0),
+            ..point(frequency_hz, spl_db)
+        }
+    }
+
+    #[test]
+    fn it_accepts_a_valid_curve() {
+        let points = vec![
+            phased_point(20.0, 90.0),
+            phased_point(1_000.0, 100.0),
+            phased_point(20_000.0, 80.0),
+        ];
+
+        assert_eq!(validate(&points), vec![]);
+        assert_eq!(Quality::from_issues(&validate(&points)), Quality::Ok);
+    }
+
+    #[test]
+    fn it_rejects_a_non_monotonic_axis() {
+        let points = vec![
+            phased_point(20.0, 90.0),
+            phased_point(20_000.0, 80.0),
+            phased_point(20_000.0, 81.0),
+            phased_point(1_000.0, 100.0),
+        ];
+
+        assert_eq!(
+            validate(&points),
+            vec![Issue::DuplicateFrequency, Issue::DescendingFrequency]
+        );
+        assert_eq!(Quality::from_issues(&validate(&points)), Quality::Error);
+    }
+
+    #[test]
+    fn it_rejects_invalid_values() {
+        let points = vec![
+            phased_point(-20.0, 90.0),
+            phased_point(1_000.0, f64::NAN),
+            phased_point(20_000.0, 0.0),
+        ];
+
+        assert_eq!(
+            validate(&points),
+            vec![
+                Issue::NonFiniteValue,
+                Issue::NegativeFrequency,
+                Issue::ZeroSpl
+            ]
+        );
+    }
+
+    #[test]
+    fn it_warns_about_suspicious_curves() {
+        let points = vec![point(100.0, 90.0), point(10_000.0, 90.5)];
+
+        assert_eq!(
+            validate(&points),
+            vec![
+                Issue::InsufficientCoverage,
+                Issue::FlatLine,
+                Issue::MissingPhase
+            ]
+        );
+        assert_eq!(Quality::from_issues(&validate(&points)), Quality::Warning);
+    }
+
+    #[test]
+    fn it_accepts_a_valid_curve_without_phase() {
+        let points = vec![point(20.0, 90.0), point(20_000.0, 80.0)];
+
+        assert_eq!(validate(&points), vec![Issue::MissingPhase]);
+        assert_eq!(Quality::from_issues(&validate(&points)), Quality::Ok);
+    }
+}
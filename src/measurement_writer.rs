@@ -0,0 +1,132 @@
+use anyhow::Error;
+use std::fmt::Write;
+
+use crate::measurement_parser::Point;
+
+#[derive(Clone, Copy, Debug)]
+pub enum Format {
+    Csv,
+    RewV5,
+    Tab,
+}
+
+pub fn write(points: &[Point], format: Format) -> Result<String, Error> {
+    let mut text = String::new();
+    let separator = match format {
+        Format::Csv => ",",
+        Format::RewV5 | Format::Tab => "\t",
+    };
+
+    if let Format::RewV5 = format {
+        text.push_str(concat!(
+            "* Measurement data measured by REW V5 (exported by wobbling-statistics ",
+            env!("CARGO_PKG_VERSION"),
+            ")\n"
+        ));
+        text.push_str("*\n");
+        if points.iter().any(|point| point.phase_degrees.is_some()) {
+            text.push_str("* Freq(Hz)\tSPL(dB)\tPhase(degrees)\n");
+        } else {
+            text.push_str("* Freq(Hz)\tSPL(dB)\n");
+        }
+    }
+    for point in points {
+        match point.phase_degrees {
+            Some(phase_degrees) => writeln!(
+                text,
+                "{}{}{}{}{}",
+                point.frequency_hz, separator, point.spl_db, separator, phase_degrees
+            ),
+            None => writeln!(text, "{}{}{}", point.frequency_hz, separator, point.spl_db),
+        }?;
+    }
+
+    Ok(text)
+}
+
+#[cfg(test)]
+mod tests {
+    use proptest::prelude::*;
+
+    use super::*;
+    use crate::measurement_parser;
+
+    fn points() -> Vec<Point> {
+        vec![
+            Point {
+                frequency_hz: 20.0,
+                phase_degrees: Some(36.7401),
+                spl_db: 96.774,
+            },
+            Point {
+                frequency_hz: 20.299999,
+                phase_degrees: Some(-36.0714),
+                spl_db: 96.813,
+            },
+        ]
+    }
+
+    #[test]
+    fn it_round_trips_csv() {
+        let text = write(&points(), Format::Csv).unwrap();
+
+        assert_eq!(text, "20,96.774,36.7401\n20.299999,96.813,-36.0714\n");
+        assert_eq!(measurement_parser::parse(&text).unwrap(), points());
+    }
+
+    #[test]
+    fn it_round_trips_rew_v5() {
+        let text = write(&points(), Format::RewV5).unwrap();
+
+        assert_eq!(measurement_parser::parse(&text).unwrap(), points());
+    }
+
+    #[test]
+    fn it_round_trips_tab() {
+        let text = write(&points(), Format::Tab).unwrap();
+
+        assert_eq!(text, "20\t96.774\t36.7401\n20.299999\t96.813\t-36.0714\n");
+        assert_eq!(measurement_parser::parse(&text).unwrap(), points());
+    }
+
+    #[test]
+    fn it_round_trips_without_phase() {
+        let points: Vec<Point> = points()
+            .into_iter()
+            .map(|point| Point {
+                phase_degrees: None,
+                ..point
+            })
+            .collect();
+
+        for format in [Format::Csv, Format::RewV5, Format::Tab] {
+            let text = write(&points, format).unwrap();
+
+            assert_eq!(measurement_parser::parse(&text).unwrap(), points);
+        }
+    }
+
+    fn arbitrary_point() -> impl Strategy<Value = Point> {
+        (
+            0.0..100_000.0,
+            proptest::option::of(-180.0..180.0),
+            -200.0..200.0,
+        )
+            .prop_map(|(frequency_hz, phase_degrees, spl_db)| Point {
+                frequency_hz,
+                phase_degrees,
+                spl_db,
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn it_round_trips_arbitrary_points(points in proptest::collection::vec(arbitrary_point(), 0..64)) {
+            for format in [Format::Csv, Format::RewV5, Format::Tab] {
+                let text = write(&points, format).unwrap();
+
+                prop_assert_eq!(&measurement_parser::parse(&text).unwrap(), &points);
+            }
+        }
+    }
+}
@@ -0,0 +1,22 @@
+use anyhow::Error;
+
+pub fn new(
+    len: usize,
+    max_title_len: usize,
+    multi_progress: &indicatif::MultiProgress,
+    title: &str,
+) -> Result<indicatif::ProgressBar, Error> {
+    let progress_bar = multi_progress
+        .insert_from_back(1, indicatif::ProgressBar::new(len.try_into()?))
+        .with_finish(indicatif::ProgressFinish::Abandon);
+    progress_bar.enable_steady_tick(std::time::Duration::from_secs_f64(0.1));
+    progress_bar.set_style(
+        indicatif::ProgressStyle::with_template(&format!(
+            "({{pos:>4}}/{{len:>4}}) ({{percent:>3}}%) {{msg:{}}} {{elapsed_precise}} [{{wide_bar}}]",
+            max_title_len
+        ))?
+        .progress_chars("#-"),
+    );
+    progress_bar.set_message(title.to_string());
+    Ok(progress_bar)
+}
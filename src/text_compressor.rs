@@ -0,0 +1,46 @@
+use anyhow::{bail, Error};
+
+const LEVEL: i32 = 3;
+
+pub fn compress(text: &str) -> Result<Vec<u8>, Error> {
+    Ok(zstd::encode_all(text.as_bytes(), LEVEL)?)
+}
+
+pub fn decompress(bytes: &[u8]) -> Result<String, Error> {
+    Ok(String::from_utf8(zstd::decode_all(bytes)?)?)
+}
+
+pub fn text(text: Option<String>, blob: Option<Vec<u8>>) -> Result<String, Error> {
+    match (text, blob) {
+        (Some(text), _) => Ok(text),
+        (None, Some(blob)) => decompress(&blob),
+        (None, None) => bail!("found a channel without text or blob"),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn it_round_trips() {
+        let text = "20\t90.1\n20000\t80.2\n".repeat(100);
+
+        let result = compress(&text).unwrap();
+
+        assert!(result.len() < text.len());
+        assert_eq!(decompress(&result).unwrap(), text);
+    }
+
+    #[test]
+    fn it_prefers_the_stored_text() {
+        let blob = compress("compressed").unwrap();
+
+        assert_eq!(
+            text(Some("stored".to_string()), Some(blob.clone())).unwrap(),
+            "stored"
+        );
+        assert_eq!(text(None, Some(blob)).unwrap(), "compressed");
+        assert!(text(None, None).is_err());
+    }
+}